@@ -0,0 +1,69 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, FnArg, GenericParam, ItemFn, ReturnType};
+
+/// Turns `fn Name(props: Props, children: Children) -> Element` into a unit struct implementing `Component`.
+///
+/// Components may be generic over their renderer (e.g. `fn Name<R: Renderer>(...) -> Result<Element<R>, JsValue>`),
+/// in which case `Component<R>` is implemented for every renderer.
+pub fn component(item: ItemFn) -> syn::Result<TokenStream> {
+    let ItemFn { attrs, vis, sig, block } = item;
+
+    let generics = &sig.generics;
+    let renderer = match (generics.params.len(), generics.params.first()) {
+        (0, _) => quote! { ::spiderweb::dom::DefaultRenderer },
+        (1, Some(GenericParam::Type(x))) => x.ident.to_token_stream(),
+        _ => return Err(syn::Error::new(generics.span(), "Components can only be generic over their renderer")),
+    };
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new(asyncness.span(), "Components can't be async"));
+    }
+
+    let mut inputs = Vec::with_capacity(sig.inputs.len());
+    for input in sig.inputs.iter() {
+        match input {
+            FnArg::Typed(x) => inputs.push(x),
+            FnArg::Receiver(x) => return Err(syn::Error::new(x.span(), "Components can't take `self`")),
+        }
+    }
+
+    let props = match inputs.first() {
+        Some(x) => x.ty.to_token_stream(),
+        None => quote! { ::spiderweb::dom::NoProps },
+    };
+    let args = match inputs.len() {
+        0 => quote! {},
+        1 => quote! { props },
+        2 => quote! { props, children },
+        _ => return Err(syn::Error::new(sig.inputs.span(), "Components take at most their props and children")),
+    };
+
+    let ident = &sig.ident;
+    let output = match sig.output {
+        ReturnType::Default => return Err(syn::Error::new(sig.span(), "Components must return an `Element`")),
+        ref x => x,
+    };
+    let inputs = &sig.inputs;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    return Ok(quote! {
+        #(#attrs)*
+        #[derive(Debug, Clone, Copy)]
+        #vis struct #ident;
+
+        impl #impl_generics ::spiderweb::dom::Component<#renderer> for #ident #where_clause {
+            type Props = #props;
+
+            #[allow(unused_variables)]
+            #[inline]
+            fn render (
+                props: Self::Props,
+                children: ::spiderweb::dom::Children<'_, #renderer>
+            ) -> ::spiderweb::std::result::Result<::spiderweb::dom::Element<#renderer>, ::spiderweb::wasm_bindgen::JsValue> {
+                #[inline(always)]
+                fn render #impl_generics (#inputs) #output #where_clause #block
+                return ::spiderweb::dom::IntoRender::into_render(render(#args));
+            }
+        }
+    });
+}
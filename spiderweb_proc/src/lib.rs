@@ -1,15 +1,36 @@
 use crate::console::Console;
-use parse::Element;
+use parse::Client;
 use quote::{quote, ToTokens};
-use syn::parse_macro_input;
+use syn::{parse_macro_input, ItemFn};
 
+mod component;
 mod console;
 mod parse;
 
+/// Builds an element out of HTML-like markup.
+///
+/// Elements are rendered by the default renderer, unless another one is specified before the markup
+/// (e.g. `client!(R => <div/>)`).
+///
+/// Attributes prefixed with `on:` add event listeners (e.g. `on:click={...}`), whilst those prefixed with `bind:`
+/// bind a property of form controls to an `Rc<Writeable<_>>` in both directions (`bind:value`, `bind:checked`,
+/// `bind:number` and `bind:selected`). Controls and forms can also be bound to the fields and forms of the `form`
+/// module (`bind:field` and `bind:form`).
 #[proc_macro]
 pub fn client(items: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let element = parse_macro_input!(items as Element);
-    return element.to_token_stream().into();
+    let client = parse_macro_input!(items as Client);
+    return client.to_token_stream().into();
+}
+
+/// Turns a function returning an `Element` into a component, usable as a capitalised tag inside `client!`.
+///
+/// The function may take the component's props, followed by its `Children`.
+#[proc_macro_attribute]
+pub fn component(_attrs: proc_macro::TokenStream, items: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(items as ItemFn);
+    return component::component(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into();
 }
 
 #[proc_macro]
@@ -17,7 +38,7 @@ pub fn println(items: proc_macro::TokenStream) -> proc_macro::TokenStream {
     return match parse_macro_input!(items as Console) {
         Console::Format(x) => {
             quote! {
-                ::spiderweb::log(&::spiderweb::wasm_bindgen::JsValue::from_str(&::std::format!(#x)))
+                ::spiderweb::log_str(&::std::format!(#x))
             }
         },
         Console::Value(x) if x.len() == 1 => {
@@ -39,7 +60,7 @@ pub fn eprintln(items: proc_macro::TokenStream) -> proc_macro::TokenStream {
     return match parse_macro_input!(items as Console) {
         Console::Format(x) => {
             quote! {
-                ::spiderweb::error(&::spiderweb::wasm_bindgen::JsValue::from_str(&::std::format!(#x)))
+                ::spiderweb::error_str(&::std::format!(#x))
             }
         },
         Console::Value(x) if x.len() == 1 => {
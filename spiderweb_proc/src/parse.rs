@@ -1,14 +1,45 @@
 use derive_syn_parse::Parse;
 use proc_macro2::{Ident, TokenStream};
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
-    braced, custom_keyword, ext::IdentExt, parse::Parse, spanned::Spanned, Expr, Path, Token,
+    braced, custom_keyword, ext::IdentExt, parse::Parse, spanned::Spanned, Expr, Pat, Path, Token, Type,
 };
 custom_keyword!(on);
+custom_keyword!(bind);
+
+/// Properties that can be bound with `bind:`, through the `Element::bind_*` method of the same name
+const BINDINGS: &[&str] = &["value", "checked", "number", "selected", "field", "form"];
+
+/// `<div>...</div>`, optionally preceded by the renderer of the elements (e.g. `R => <div>...</div>`)
+pub struct Client {
+    pub renderer: Option<Type>,
+    pub element: Element,
+}
 
 pub enum Content {
     Element(Element),
     Expr(Expr),
+    If(If),
+    Match(Match),
+}
+
+/// `if cond { <a/> } else { <b/> }`, where `cond` is a `&Readable<bool>`
+pub struct If {
+    pub cond: Expr,
+    pub then: Element,
+    pub otherwise: Option<Element>,
+}
+
+/// `match state { pattern => <a/>, ... }`, where `state` is a `&Readable<T>`
+pub struct Match {
+    pub state: Expr,
+    pub arms: Vec<Arm>,
+}
+
+pub struct Arm {
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub body: Element,
 }
 
 pub struct Element {
@@ -17,10 +48,18 @@ pub struct Element {
     pub close: Option<CloseTag>,
 }
 
+/// Prefix of an attribute, which changes how its value is applied
+pub enum Directive {
+    /// `on:click={...}` adds an event listener
+    On(on),
+    /// `bind:value={cell}` binds a property to a cell in both directions
+    Bind(bind),
+}
+
 #[derive(Parse)]
 pub struct Attribute {
-    #[call(parse_on)]
-    pub on_token: Option<(on, Token![:])>,
+    #[call(parse_directive)]
+    pub directive: Option<(Directive, Token![:])>,
     pub name: Ident,
     #[peek(Token![=])]
     pub value: Option<AttributeValue>,
@@ -41,6 +80,8 @@ pub struct OpenTag {
     pub path: Path,
     #[call(parse_attributes)]
     pub attrs: Vec<Attribute>,
+    /// Fills the missing props of a component with their defaults
+    pub rest: Option<Token![..]>,
     pub end_bracket: Option<Token![/]>,
     pub close_bracket: Token![>],
 }
@@ -66,20 +107,183 @@ impl Content {
             let content;
             braced!(content in input);
             return Ok(Some(Self::Expr(content.parse::<Expr>()?)));
+        } else if input.peek(Token![if]) {
+            return Ok(Some(Self::If(input.parse::<If>()?)));
+        } else if input.peek(Token![match]) {
+            return Ok(Some(Self::Match(input.parse::<Match>()?)));
         }
 
         return Err(input.error("Unknown element"));
     }
 
     #[inline]
-    fn render(&self) -> TokenStream {
+    fn render(&self, renderer: &TokenStream) -> TokenStream {
         match self {
-            Self::Element(x) => x.to_token_stream(),
+            Self::Element(x) => {
+                let x = x.render(renderer);
+                quote! { #x? }
+            }
             Self::Expr(x) => x.to_token_stream(),
+            Self::If(x) => x.render(renderer),
+            Self::Match(x) => x.render(renderer),
         }
     }
 }
 
+impl If {
+    #[inline]
+    fn render(&self, renderer: &TokenStream) -> TokenStream {
+        let Self { cond, then, otherwise } = self;
+        let then = then.render(renderer);
+        let otherwise = match otherwise {
+            Some(x) => {
+                let x = x.render(renderer);
+                quote! { ::spiderweb::dom::render_branch(#x) }
+            }
+            None => quote! { ::spiderweb::std::option::Option::None },
+        };
+
+        return quote! {
+            ::spiderweb::dom::Switch::new(
+                #cond,
+                |x: &bool| *x,
+                move |x: &bool| match *x {
+                    true => ::spiderweb::dom::render_branch(#then),
+                    false => #otherwise,
+                }
+            )
+        };
+    }
+}
+
+impl Match {
+    #[inline]
+    fn render(&self, renderer: &TokenStream) -> TokenStream {
+        let state = &self.state;
+        let keys = self.arms.iter().enumerate().map(|(i, Arm { pat, guard, .. })| {
+            let guard = guard.as_ref().map(|x| quote! { if #x });
+            quote! { #pat #guard => #i }
+        });
+        let branches = self.arms.iter().map(|Arm { pat, guard, body }| {
+            let guard = guard.as_ref().map(|x| quote! { if #x });
+            let body = body.render(renderer);
+            quote! { #pat #guard => ::spiderweb::dom::render_branch(#body) }
+        });
+
+        return quote! {
+            ::spiderweb::dom::Switch::new(
+                #state,
+                {
+                    #[allow(unused_variables)]
+                    let key = |x: &_| match x { #(#keys),* };
+                    key
+                },
+                move |x: &_| match x { #(#branches),* }
+            )
+        };
+    }
+}
+
+impl Parse for If {
+    #[inline]
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![if]>()?;
+        let cond = Expr::parse_without_eager_brace(input)?;
+        let then = parse_branch(input)?;
+
+        let otherwise = match input.peek(Token![else]) {
+            true => {
+                let else_token = input.parse::<Token![else]>()?;
+                if input.peek(Token![if]) {
+                    return Err(syn::Error::new(
+                        else_token.span(),
+                        "`else if` isn't supported, use `match` instead",
+                    ));
+                }
+                Some(parse_branch(input)?)
+            }
+            false => None,
+        };
+
+        return Ok(Self { cond, then, otherwise });
+    }
+}
+
+impl Parse for Match {
+    #[inline]
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![match]>()?;
+        let state = Expr::parse_without_eager_brace(input)?;
+
+        let content;
+        braced!(content in input);
+
+        let mut arms = Vec::new();
+        while !content.is_empty() {
+            let pat = parse_pattern(&content)?;
+            let guard = match content.peek(Token![if]) {
+                true => {
+                    content.parse::<Token![if]>()?;
+                    Some(content.parse::<Expr>()?)
+                }
+                false => None,
+            };
+            content.parse::<Token![=>]>()?;
+
+            // As in Rust, the comma is optional after a braced arm
+            let braced = content.peek(syn::token::Brace);
+            let body = match braced {
+                true => parse_branch(&content)?,
+                false => content.parse::<Element>()?,
+            };
+            arms.push(Arm { pat, guard, body });
+
+            if braced {
+                content.parse::<Option<Token![,]>>()?;
+            } else if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        return Ok(Self { state, arms });
+    }
+}
+
+/// Parses the pattern of a `match` arm, which may contain alternatives separated by `|`
+#[inline]
+fn parse_pattern(input: syn::parse::ParseStream) -> syn::Result<Pat> {
+    let leading_vert = input.parse::<Option<Token![|]>>()?;
+    let pat = input.parse::<Pat>()?;
+    if leading_vert.is_none() && !input.peek(Token![|]) {
+        return Ok(pat);
+    }
+
+    let mut cases = syn::punctuated::Punctuated::new();
+    cases.push_value(pat);
+    while input.peek(Token![|]) {
+        cases.push_punct(input.parse()?);
+        cases.push_value(input.parse()?);
+    }
+
+    return Ok(Pat::Or(syn::PatOr {
+        attrs: Vec::new(),
+        leading_vert,
+        cases,
+    }));
+}
+
+/// Parses a branch of an `if` or `match`, which must contain a single element
+#[inline]
+fn parse_branch(input: syn::parse::ParseStream) -> syn::Result<Element> {
+    let content;
+    braced!(content in input);
+    let element = content.parse::<Element>()?;
+    if !content.is_empty() {
+        return Err(content.error("Branches must contain a single element"));
+    }
+    return Ok(element);
+}
+
 impl Parse for Element {
     #[inline]
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -132,32 +336,69 @@ impl Parse for Element {
     }
 }
 
-impl ToTokens for Element {
+impl Parse for Client {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let renderer = match input.peek(Token![<]) {
+            true => None,
+            false => {
+                let renderer = input.parse::<Type>()?;
+                input.parse::<Token![=>]>()?;
+                Some(renderer)
+            }
+        };
+
+        return Ok(Self {
+            renderer,
+            element: input.parse()?,
+        });
+    }
+}
+
+impl ToTokens for Client {
     #[inline]
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        return match self.open.path.get_ident() {
+        let renderer = match self.renderer {
+            Some(ref x) => x.to_token_stream(),
+            None => quote! { ::spiderweb::dom::DefaultRenderer },
+        };
+        tokens.extend(self.element.render(&renderer));
+    }
+}
+
+impl Element {
+    /// Builds the element with the specified renderer.
+    #[inline]
+    fn render(&self, renderer: &TokenStream) -> TokenStream {
+        let mut tokens = TokenStream::new();
+        match self.open.path.get_ident() {
             Some(x) if x.to_string().starts_with(char::is_lowercase) => {
-                client_primitive(self, tokens)
+                client_primitive(self, renderer, &mut tokens)
             }
-            _ => client_component(self, tokens),
+            _ => client_component(self, renderer, &mut tokens),
         }
-        .into();
+        return tokens;
     }
 }
 
-fn client_primitive(Element { open, content, .. }: &Element, tokens: &mut TokenStream) {
+fn client_primitive(Element { open, content, .. }: &Element, renderer: &TokenStream, tokens: &mut TokenStream) {
+    if let Some(rest) = open.rest {
+        return tokens.extend(
+            syn::Error::new(rest.span(), "Only components have default props").to_compile_error(),
+        );
+    }
+
     let path = &open.path;
-    let mut my_tokens = quote! { ::spiderweb::dom::Element::stateless(stringify!(#path)) };
+    let mut my_tokens = quote! { ::spiderweb::dom::Element::<#renderer>::stateless(stringify!(#path)) };
 
     // Content
     for content in content.iter() {
-        let content = content.render();
+        let content = content.render(renderer);
         my_tokens.extend(quote! { .append_child_inner(#content)? });
     }
 
     // Attributes
     for Attribute {
-        on_token,
+        directive,
         name,
         value,
     } in open.attrs.iter()
@@ -166,12 +407,22 @@ fn client_primitive(Element { open, content, .. }: &Element, tokens: &mut TokenS
             .as_ref()
             .map_or_else(|| name.to_token_stream(), |x| x.value.to_token_stream());
 
-        match on_token.is_some() {
-            true => {
+        match directive {
+            Some((Directive::On(_), _)) => {
                 my_tokens.extend(quote! { .set_callback_inner(stringify!(#name), #value) })
             },
-            false => {
-                my_tokens.extend(quote! { .set_attribute_inner(stringify!(#name), #value, ::spiderweb::std::string::ToString::to_string)? });
+            Some((Directive::Bind(_), _)) => {
+                if !BINDINGS.contains(&name.to_string().as_str()) {
+                    return tokens.extend(
+                        syn::Error::new(name.span(), format!("Unknown binding, expected one of: {}", BINDINGS.join(", ")))
+                            .to_compile_error(),
+                    );
+                }
+                let method = format_ident!("bind_{}", name);
+                my_tokens.extend(quote! { .bind_inner(|element| element.#method(&#value))? });
+            },
+            None => {
+                my_tokens.extend(quote! { .set_attribute_inner(stringify!(#name), #value)? });
             }
         }
     }
@@ -181,7 +432,7 @@ fn client_primitive(Element { open, content, .. }: &Element, tokens: &mut TokenS
     })
 }
 
-fn client_component(Element { open, content, .. }: &Element, tokens: &mut TokenStream) {
+fn client_component(Element { open, content, .. }: &Element, renderer: &TokenStream, tokens: &mut TokenStream) {
     let path = &open.path;
     let attrs = open.attrs.iter().map(|Attribute { name, value, .. }| {
         let value = value
@@ -191,21 +442,28 @@ fn client_component(Element { open, content, .. }: &Element, tokens: &mut TokenS
            #name #value
         }
     });
+    let rest = open
+        .rest
+        .map(|_| quote! { ..::spiderweb::std::default::Default::default() });
 
-    if content.is_empty() {
-        return tokens.extend(quote! {
-            ::spiderweb::dom::Component::render(#path { #(#attrs),* })
-        });
-    }
+    let children = match content.is_empty() {
+        true => quote! { ::spiderweb::dom::Children::none() },
+        false => {
+            let content = content.iter().map(|x| x.render(renderer));
+            quote! {
+                ::spiderweb::dom::Children::new(|parent: &::spiderweb::dom::Element<#renderer>| {
+                    #(::spiderweb::dom::element::Content::append_to(#content, parent)?;)*
+                    ::spiderweb::std::result::Result::<(), ::spiderweb::wasm_bindgen::JsValue>::Ok(())
+                })
+            }
+        }
+    };
 
-    let content = content.iter().map(Content::render);
     tokens.extend(quote! {
-        (|| {
-            ::std::result::Result::<::spiderweb::dom::Element::<_>, ::spiderweb::wasm_bindgen::JsValue>::Ok(
-                ::spiderweb::dom::Component::render(#path { #(#attrs),* })?.
-                #(append_child_inner(#content)?).*
-            )
-        })()
+        ::spiderweb::dom::render::<#path, #renderer>(
+            ::spiderweb::dom::PropsOf::<#path, #renderer> { #(#attrs,)* #rest },
+            #children
+        )
     });
 }
 
@@ -219,11 +477,16 @@ fn parse_attributes(input: syn::parse::ParseStream) -> syn::Result<Vec<Attribute
 }
 
 #[inline]
-fn parse_on(input: syn::parse::ParseStream) -> syn::Result<Option<(on, Token![:])>> {
-    if input.peek(on) {
-        let on = input.parse()?;
-        let token = input.parse()?;
-        return Ok(Some((on, token)));
+fn parse_directive(input: syn::parse::ParseStream) -> syn::Result<Option<(Directive, Token![:])>> {
+    if !input.peek2(Token![:]) {
+        return Ok(None);
     }
-    return Ok(None);
+
+    let directive = match (input.peek(on), input.peek(bind)) {
+        (true, _) => Directive::On(input.parse()?),
+        (_, true) => Directive::Bind(input.parse()?),
+        _ => return Ok(None),
+    };
+    let token = input.parse()?;
+    return Ok(Some((directive, token)));
 }
@@ -0,0 +1,295 @@
+use super::{element::Element, renderer::Renderer};
+use crate::state::{Readable, Writeable};
+use std::{borrow::Cow, rc::Rc};
+use wasm_bindgen::JsValue;
+
+/// A value that can be assigned to an attribute.
+pub trait AttributeValue {
+    /// Returns the value of the attribute, or `None` if the attribute must be removed.
+    fn to_attribute (&self) -> Option<Cow<'_, str>>;
+}
+
+/// A primitive value of an element's property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Property {
+    Null,
+    Bool (bool),
+    Number (f64),
+    String (String),
+    /// Any JavaScript value
+    #[cfg(target_family = "wasm")]
+    Js (JsValue),
+}
+
+/// A value that can be assigned to a property of an element.
+pub trait PropertyValue {
+    fn to_property (&self) -> Property;
+}
+
+/// A value that can be assigned to an attribute by the [`client!`](crate::client) macro.
+///
+/// Plain values are assigned once, whilst cells are bound to the attribute, updating it whenever they change.
+pub trait IntoAttribute {
+    fn set_to<R: Renderer> (self, element: &Element<R>, name: &str) -> Result<(), JsValue>;
+}
+
+impl<R: Renderer> Element<R> {
+    #[inline]
+    pub fn set_attribute (&self, name: &str, value: &str) -> Result<(), JsValue> {
+        R::set_attribute(self.html_node(), name, value)
+    }
+
+    #[inline]
+    pub fn remove_attribute (&self, name: &str) -> Result<(), JsValue> {
+        R::remove_attribute(self.html_node(), name)
+    }
+
+    /// Sets a property of the underlying JavaScript object (e.g. `value` or `checked`).
+    #[inline]
+    pub fn set_property (&self, name: &str, value: impl PropertyValue) -> Result<(), JsValue> {
+        R::set_property(self.html_node(), name, &value.to_property())
+    }
+
+    /// Binds the attribute `name` to `state`, updating it whenever the cell changes.
+    pub fn bind_attribute<T: ?Sized + 'static + AttributeValue> (&self, name: &str, state: &Readable<T>) -> Result<(), JsValue> {
+        state.with(|x| apply_attribute::<R, _>(self.html_node(), name, x))?;
+
+        let element = self.html_node().clone();
+        let name = name.to_owned();
+        let sub = state.subscribe(move |x| {
+            if let Err(e) = apply_attribute::<R, _>(&element, &name, x) {
+                crate::eprintln!(&e)
+            }
+        });
+
+        unsafe { &mut *self.inner.get() }.subscriptions.push(sub);
+        return Ok(())
+    }
+
+    /// Binds the property `name` to `state`, updating it whenever the cell changes.
+    pub fn bind_property<T: ?Sized + 'static + PropertyValue> (&self, name: &str, state: &Readable<T>) -> Result<(), JsValue> {
+        state.with(|x| self.set_property(name, x))?;
+
+        let element = self.html_node().clone();
+        let name = name.to_owned();
+        let sub = state.subscribe(move |x| {
+            if let Err(e) = R::set_property(&element, &name, &x.to_property()) {
+                crate::eprintln!(&e)
+            }
+        });
+
+        unsafe { &mut *self.inner.get() }.subscriptions.push(sub);
+        return Ok(())
+    }
+
+    /// Adds the class `name` to the element while `state` is `true`, and removes it while it's `false`.
+    pub fn bind_class (&self, name: &str, state: &Readable<bool>) -> Result<(), JsValue> {
+        let element = self.html_node().clone();
+        R::toggle_class(&element, name, state.get())?;
+
+        let name = name.to_owned();
+        let sub = state.subscribe(move |x| {
+            if let Err(e) = R::toggle_class(&element, &name, *x) {
+                crate::eprintln!(&e)
+            }
+        });
+
+        unsafe { &mut *self.inner.get() }.subscriptions.push(sub);
+        return Ok(())
+    }
+
+    /// Binds the CSS property `prop` of the element's inline style to `state`.
+    pub fn bind_style<T: ?Sized + 'static + AsRef<str>> (&self, prop: &str, state: &Readable<T>) -> Result<(), JsValue> {
+        let element = self.html_node().clone();
+        state.with(|x| R::set_style(&element, prop, x.as_ref()))?;
+
+        let prop = prop.to_owned();
+        let sub = state.subscribe(move |x| {
+            if let Err(e) = R::set_style(&element, &prop, x.as_ref()) {
+                crate::eprintln!(&e)
+            }
+        });
+
+        unsafe { &mut *self.inner.get() }.subscriptions.push(sub);
+        return Ok(())
+    }
+}
+
+#[inline]
+fn apply_attribute<R: Renderer, T: ?Sized + AttributeValue> (element: &R::Element, name: &str, value: &T) -> Result<(), JsValue> {
+    match value.to_attribute() {
+        Some(value) => R::set_attribute(element, name, &value),
+        None => R::remove_attribute(element, name),
+    }
+}
+
+/* ATTRIBUTE VALUE */
+impl AttributeValue for str {
+    #[inline]
+    fn to_attribute (&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl AttributeValue for String {
+    #[inline]
+    fn to_attribute (&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+/// Boolean attributes are present while `true`, and removed while `false`.
+impl AttributeValue for bool {
+    #[inline]
+    fn to_attribute (&self) -> Option<Cow<'_, str>> {
+        self.then_some(Cow::Borrowed(""))
+    }
+}
+
+impl<T: AttributeValue> AttributeValue for Option<T> {
+    #[inline]
+    fn to_attribute (&self) -> Option<Cow<'_, str>> {
+        self.as_ref().and_then(T::to_attribute)
+    }
+}
+
+impl<T: ?Sized + AttributeValue> AttributeValue for &T {
+    #[inline]
+    fn to_attribute (&self) -> Option<Cow<'_, str>> {
+        T::to_attribute(self)
+    }
+}
+
+macro_rules! impl_attribute_value {
+    ($($t:ty),+) => {
+        $(
+            impl AttributeValue for $t {
+                #[inline]
+                fn to_attribute (&self) -> Option<Cow<'_, str>> {
+                    Some(Cow::Owned(self.to_string()))
+                }
+            }
+        )+
+    };
+}
+
+impl_attribute_value! {
+    char,
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64
+}
+
+/* PROPERTY VALUE */
+impl PropertyValue for Property {
+    #[inline]
+    fn to_property (&self) -> Property {
+        self.clone()
+    }
+}
+
+impl PropertyValue for bool {
+    #[inline]
+    fn to_property (&self) -> Property {
+        Property::Bool(*self)
+    }
+}
+
+impl PropertyValue for str {
+    #[inline]
+    fn to_property (&self) -> Property {
+        Property::String(self.to_owned())
+    }
+}
+
+impl PropertyValue for String {
+    #[inline]
+    fn to_property (&self) -> Property {
+        Property::String(self.clone())
+    }
+}
+
+impl PropertyValue for char {
+    #[inline]
+    fn to_property (&self) -> Property {
+        Property::String(self.to_string())
+    }
+}
+
+/// `None` is assigned as `null`.
+impl<T: PropertyValue> PropertyValue for Option<T> {
+    #[inline]
+    fn to_property (&self) -> Property {
+        self.as_ref().map_or(Property::Null, T::to_property)
+    }
+}
+
+impl<T: ?Sized + PropertyValue> PropertyValue for &T {
+    #[inline]
+    fn to_property (&self) -> Property {
+        T::to_property(self)
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl PropertyValue for JsValue {
+    #[inline]
+    fn to_property (&self) -> Property {
+        Property::Js(self.clone())
+    }
+}
+
+macro_rules! impl_property_value {
+    ($($t:ty),+) => {
+        $(
+            impl PropertyValue for $t {
+                #[inline]
+                fn to_property (&self) -> Property {
+                    Property::Number(*self as f64)
+                }
+            }
+        )+
+    };
+}
+
+impl_property_value! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64
+}
+
+/* INTO ATTRIBUTE */
+impl<T: AttributeValue> IntoAttribute for T {
+    #[inline]
+    fn set_to<R: Renderer> (self, element: &Element<R>, name: &str) -> Result<(), JsValue> {
+        apply_attribute::<R, _>(element.html_node(), name, &self)
+    }
+}
+
+impl<T: ?Sized + 'static + AttributeValue> IntoAttribute for &Readable<T> {
+    #[inline]
+    fn set_to<R: Renderer> (self, element: &Element<R>, name: &str) -> Result<(), JsValue> {
+        element.bind_attribute(name, self)
+    }
+}
+
+impl<T: ?Sized + 'static + AttributeValue> IntoAttribute for &Writeable<T> {
+    #[inline]
+    fn set_to<R: Renderer> (self, element: &Element<R>, name: &str) -> Result<(), JsValue> {
+        element.bind_attribute(name, self)
+    }
+}
+
+impl<T: ?Sized + 'static + AttributeValue> IntoAttribute for &Rc<Readable<T>> {
+    #[inline]
+    fn set_to<R: Renderer> (self, element: &Element<R>, name: &str) -> Result<(), JsValue> {
+        element.bind_attribute(name, self)
+    }
+}
+
+impl<T: ?Sized + 'static + AttributeValue> IntoAttribute for &Rc<Writeable<T>> {
+    #[inline]
+    fn set_to<R: Renderer> (self, element: &Element<R>, name: &str) -> Result<(), JsValue> {
+        element.bind_attribute(name, self)
+    }
+}
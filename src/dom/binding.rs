@@ -0,0 +1,113 @@
+use super::{attribute::Property, element::Element, renderer::Renderer};
+use crate::state::Writeable;
+use std::{cell::RefCell, rc::Rc, str::FromStr};
+use wasm_bindgen::JsValue;
+
+impl<R: Renderer> Element<R> {
+    /// Binds the `value` of the element (e.g. an `<input>` or a `<textarea>`) to `state` in both directions.
+    ///
+    /// The cell is updated on every `input` event, and the element whenever the cell changes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spiderweb::{client, state::Writeable};
+    /// use std::rc::Rc;
+    ///
+    /// let name = Rc::new(Writeable::new(String::from("Alex")));
+    /// let input = client! { <input bind:value={name} /> }.unwrap();
+    /// # #[cfg(not(target_family = "wasm"))] {
+    /// input.html_node().input("Sam");
+    /// assert_eq!(name.with(Clone::clone), "Sam");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn bind_value (&self, state: &Rc<Writeable<String>>) -> Result<(), JsValue> {
+        self.bind_two_way(state, "input", "value", |x| Property::String(x.clone()), into_string)
+    }
+
+    /// Binds the `checked` state of the element (e.g. a checkbox) to `state` in both directions.
+    ///
+    /// The cell is updated on every `change` event, and the element whenever the cell changes.
+    #[inline]
+    pub fn bind_checked (&self, state: &Rc<Writeable<bool>>) -> Result<(), JsValue> {
+        self.bind_two_way(state, "change", "checked", |x| Property::Bool(*x), |x| match x {
+            Property::Bool(x) => Some(x),
+            _ => None,
+        })
+    }
+
+    /// Binds the `value` of the element (e.g. an `<input type="number">`) to `state` in both directions, parsing it
+    /// on every `input` event.
+    ///
+    /// Values that can't be parsed (e.g. while the user is still typing) leave the cell untouched.
+    #[inline]
+    pub fn bind_number<T: 'static + FromStr + ToString> (&self, state: &Rc<Writeable<T>>) -> Result<(), JsValue> {
+        self.bind_two_way(state, "input", "value", |x| Property::String(x.to_string()), |x| {
+            into_string(x)?.trim().parse().ok()
+        })
+    }
+
+    /// Binds the value of the selected option of a `<select>` to `state` in both directions.
+    ///
+    /// The cell is updated on every `change` event, and the selection whenever the cell changes. The options must
+    /// be rendered before the binding is made, which [`client!`](crate::client) does for its content.
+    #[inline]
+    pub fn bind_selected (&self, state: &Rc<Writeable<String>>) -> Result<(), JsValue> {
+        self.bind_two_way(state, "change", "value", |x| Property::String(x.clone()), into_string)
+    }
+
+    /// Binds the property `prop` to `state`, reading it back into the cell whenever `event` is dispatched.
+    ///
+    /// Updates that originate from the element aren't written back to it while it still holds the value it
+    /// wrote, since that would reset its state (e.g. the position of the cursor, or the formatting of a number).
+    fn bind_two_way<T: 'static> (
+        &self,
+        state: &Rc<Writeable<T>>,
+        event: &'static str,
+        prop: &'static str,
+        to_property: fn(&T) -> Property,
+        from_property: fn(Property) -> Option<T>,
+    ) -> Result<(), JsValue> {
+        let element = self.html_node().clone();
+        state.with(|x| R::set_property(&element, prop, &to_property(x)))?;
+
+        // Property last read from the element, alongside the property of the value it was parsed into
+        let written = Rc::new(RefCell::new(None::<(Property, Property)>));
+        let my_written = written.clone();
+        let sub = state.subscribe(move |x| {
+            let value = to_property(x);
+            let echo = my_written.borrow().as_ref().is_some_and(|(raw, parsed)| {
+                *parsed == value && R::get_property(&element, prop).as_ref() == Some(raw)
+            });
+            if echo {
+                return
+            }
+            if let Err(e) = R::set_property(&element, prop, &value) {
+                crate::eprintln!(&e)
+            }
+        });
+
+        let element = self.html_node().clone();
+        let state = Rc::downgrade(state);
+        self.add_event_listener_boxed(event, Box::new(move |_| {
+            let Some(state) = state.upgrade() else { return };
+            let Some(raw) = R::get_property(&element, prop) else { return };
+            if let Some(value) = from_property(raw.clone()) {
+                *written.borrow_mut() = Some((raw, to_property(&value)));
+                state.set(value);
+            }
+        }));
+
+        unsafe { &mut *self.inner.get() }.subscriptions.push(sub);
+        return Ok(())
+    }
+}
+
+#[inline]
+fn into_string (value: Property) -> Option<String> {
+    match value {
+        Property::String(x) => Some(x),
+        Property::Number(x) => Some(x.to_string()),
+        _ => None,
+    }
+}
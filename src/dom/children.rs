@@ -0,0 +1,65 @@
+use super::{element::{Content, Element}, renderer::{DefaultRenderer, Renderer}};
+use wasm_bindgen::JsValue;
+
+/// The content placed between the opening and closing tags of a [`Component`](super::Component).
+///
+/// Children are rendered once they're appended to an element (e.g. by placing them inside of [`client!`](crate::client)).
+pub struct Children<'a, R: Renderer = DefaultRenderer> {
+    #[allow(clippy::type_complexity)]
+    inner: Option<Box<dyn 'a + FnOnce(&Element<R>) -> Result<(), JsValue>>>,
+}
+
+impl<'a, R: Renderer> Children<'a, R> {
+    #[inline]
+    pub fn new (f: impl 'a + FnOnce(&Element<R>) -> Result<(), JsValue>) -> Self {
+        return Self { inner: Some(Box::new(f)) }
+    }
+
+    #[inline]
+    pub fn none () -> Self {
+        return Self { inner: None }
+    }
+
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<R: Renderer> Default for Children<'_, R> {
+    #[inline]
+    fn default () -> Self {
+        Self::none()
+    }
+}
+
+impl<R: Renderer> Content<R> for Children<'_, R> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        match self.inner {
+            Some(f) => f(parent),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Converts the return value of a [`component`](crate::component) function into the result of
+/// [`Component::render`](super::Component::render).
+#[doc(hidden)]
+pub trait IntoRender<R: Renderer = DefaultRenderer> {
+    fn into_render (self) -> Result<Element<R>, JsValue>;
+}
+
+impl<R: Renderer> IntoRender<R> for Element<R> {
+    #[inline]
+    fn into_render (self) -> Result<Element<R>, JsValue> {
+        Ok(self)
+    }
+}
+
+impl<R: Renderer> IntoRender<R> for Result<Element<R>, JsValue> {
+    #[inline]
+    fn into_render (self) -> Result<Element<R>, JsValue> {
+        self
+    }
+}
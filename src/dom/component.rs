@@ -1,4 +1,4 @@
-use super::element::{Element, MountedElement};
+use super::{element::{Element, ListenerRef, MountedElement}, event::{Event, EventType, FromEvent}, renderer::{DefaultRenderer, Renderer}};
 use std::{
     any::Any,
     cell::UnsafeCell,
@@ -10,8 +10,8 @@ use pin_project::pin_project;
 use wasm_bindgen::JsValue;
 
 #[pin_project(!Unpin)]
-pub struct Component<T: ?Sized> {
-    pub(super) element: Element,
+pub struct Component<T: ?Sized, R: Renderer = DefaultRenderer> {
+    pub(super) element: Element<R>,
     #[pin] state: UnsafeCell<T>,
 }
 
@@ -20,16 +20,16 @@ pub struct MountedComponent<'a, P: 'a, T: ?Sized> {
     pub(super) _phtm: PhantomData<&'a T>,
 }
 
-pub struct ComponentChild<'e, 's, T: ?Sized> {
-    element: MountedElement<&'e Element>,
+pub struct ComponentChild<'e, 's, T: ?Sized, R: Renderer = DefaultRenderer> {
+    element: MountedElement<&'e Element<R>>,
     state: Pin<&'s UnsafeCell<T>>,
 }
 
-impl<T> Component<T> {
+impl<T, R: Renderer> Component<T, R> {
     #[inline]
     pub(super) fn new(tag: &str, state: T) -> Self {
         return Self {
-            element: Element::new(tag),
+            element: Element::create(tag),
             state: UnsafeCell::new(state)
         };
     }
@@ -37,8 +37,8 @@ impl<T> Component<T> {
     #[inline]
     pub fn append_child(
         self: Pin<&Self>,
-        element: Element,
-    ) -> Result<ComponentChild<'_, '_, T>, JsValue> {
+        element: Element<R>,
+    ) -> Result<ComponentChild<'_, '_, T, R>, JsValue> {
         let this = self.project_ref();
         let element = this.element.append_child(element)?;
         return Ok(ComponentChild {
@@ -48,42 +48,81 @@ impl<T> Component<T> {
     }
 
     #[inline]
-    pub fn add_event_listener<F: FnMut(&mut T)>(self: Pin<&Self>, event: &'static str, mut f: F) where T: Unpin {
+    pub fn add_event_listener<E: EventType>(self: Pin<&Self>, mut f: impl FnMut(E::Event, &mut T)) -> ListenerRef<'_, R> where T: Unpin {
         let this = self.project_ref();
 
         let state = this.state;
-        let f = move || unsafe {
-            f(&mut *state.get());
+        let f = move |ev: Event| unsafe {
+            f(E::Event::from_event(ev), &mut *state.get());
         };
 
         let f = unsafe {
-            core::mem::transmute::<Box<dyn FnMut()>, Box<dyn 'static + FnMut()>>(Box::new(f))
+            core::mem::transmute::<Box<dyn FnMut(Event)>, Box<dyn 'static + FnMut(Event)>>(Box::new(f))
+        };
+        return this.element.add_event_listener_boxed(E::NAME, f);
+    }
+
+    #[inline]
+    pub fn add_event_listener_pinned<E: EventType>(self: Pin<&Self>, mut f: impl FnMut(E::Event, Pin<&mut T>)) -> ListenerRef<'_, R> {
+        let this = self.project_ref();
+
+        let state = this.state;
+        let f = move |ev: Event| unsafe {
+            f(E::Event::from_event(ev), Pin::new_unchecked(&mut *Pin::into_inner_unchecked(state).get()));
+        };
+
+        let f = unsafe {
+            core::mem::transmute::<Box<dyn FnMut(Event)>, Box<dyn 'static + FnMut(Event)>>(Box::new(f))
         };
-        let _handle = this.element.add_event_listener(event, f);
+        return this.element.add_event_listener_boxed(E::NAME, f);
     }
 
+    /// Invokes `f` with the component's state every time the component is attached to the document.
     #[inline]
-    pub fn add_event_listener_pinned<F: FnMut(Pin<&mut T>)>(self: Pin<&Self>, event: &'static str, mut f: F) {
+    pub fn on_mount(self: Pin<&Self>, mut f: impl FnMut(&mut T)) where T: Unpin {
         let this = self.project_ref();
 
         let state = this.state;
-        let f = move || unsafe {
-            f(Pin::new_unchecked(&mut *Pin::into_inner_unchecked(state).get()));
+        let f = move || unsafe { f(&mut *state.get()) };
+        let f = unsafe {
+            core::mem::transmute::<Box<dyn FnMut()>, Box<dyn 'static + FnMut()>>(Box::new(f))
         };
+        this.element.on_mount_boxed(f);
+    }
 
+    /// Invokes `f` with the component's state every time the component is detached from the document.
+    #[inline]
+    pub fn on_unmount(self: Pin<&Self>, mut f: impl FnMut(&mut T)) where T: Unpin {
+        let this = self.project_ref();
+
+        let state = this.state;
+        let f = move || unsafe { f(&mut *state.get()) };
         let f = unsafe {
             core::mem::transmute::<Box<dyn FnMut()>, Box<dyn 'static + FnMut()>>(Box::new(f))
         };
-        let _handle = this.element.add_event_listener(event, f);
+        this.element.on_unmount_boxed(f);
+    }
+
+    /// Invokes `f` with the component's state once, right before the component is dropped.
+    #[inline]
+    pub fn on_cleanup(self: Pin<&Self>, f: impl FnOnce(&mut T)) where T: Unpin {
+        let this = self.project_ref();
+
+        let state = this.state;
+        let f = move || unsafe { f(&mut *state.get()) };
+        let f = unsafe {
+            core::mem::transmute::<Box<dyn FnOnce()>, Box<dyn 'static + FnOnce()>>(Box::new(f))
+        };
+        this.element.on_cleanup_boxed(f);
     }
 }
 
-impl<'e, 's, T> ComponentChild<'e, 's, T> {
+impl<'e, 's, T, R: Renderer> ComponentChild<'e, 's, T, R> {
     #[inline]
     pub fn append_child<'e1>(
         &'e1 self,
-        element: Element,
-    ) -> Result<ComponentChild<'e1, 's, T>, JsValue>
+        element: Element<R>,
+    ) -> Result<ComponentChild<'e1, 's, T, R>, JsValue>
     where
         'e: 'e1,
     {
@@ -95,49 +134,49 @@ impl<'e, 's, T> ComponentChild<'e, 's, T> {
     }
 
     #[inline]
-    pub fn add_event_listener<F: FnMut(&mut T)>(&self, event: &'static str, mut f: F)
+    pub fn add_event_listener<E: EventType>(&self, mut f: impl FnMut(E::Event, &mut T)) -> ListenerRef<'_, R>
     where
         T: Unpin,
     {
         let state = self.state;
-        let f = move || unsafe {
-            f(&mut *state.get());
+        let f = move |ev: Event| unsafe {
+            f(E::Event::from_event(ev), &mut *state.get());
         };
 
         let f = unsafe {
-            core::mem::transmute::<Box<dyn FnMut()>, Box<dyn 'static + FnMut()>>(Box::new(f))
+            core::mem::transmute::<Box<dyn FnMut(Event)>, Box<dyn 'static + FnMut(Event)>>(Box::new(f))
         };
 
-        let _handle = self.element.add_event_listener(event, f);
+        return self.element.add_event_listener_boxed(E::NAME, f);
     }
 
     #[inline]
-    pub fn add_event_listener_pinned<F: FnMut(Pin<&mut T>)>(&self, event: &'static str, mut f: F) {
+    pub fn add_event_listener_pinned<E: EventType>(&self, mut f: impl FnMut(E::Event, Pin<&mut T>)) -> ListenerRef<'_, R> {
         let state = self.state;
-        let f = move || unsafe {
-            f(Pin::new_unchecked(&mut *state.get()));
+        let f = move |ev: Event| unsafe {
+            f(E::Event::from_event(ev), Pin::new_unchecked(&mut *state.get()));
         };
 
         let f = unsafe {
-            core::mem::transmute::<Box<dyn FnMut()>, Box<dyn 'static + FnMut()>>(Box::new(f))
+            core::mem::transmute::<Box<dyn FnMut(Event)>, Box<dyn 'static + FnMut(Event)>>(Box::new(f))
         };
-        let _handle = self.element.add_event_listener(event, f);
+        return self.element.add_event_listener_boxed(E::NAME, f);
     }
 }
 
-impl<'a, P: 'a + Deref<Target = Element>, T> Deref for MountedComponent<'a, P, T> {
-    type Target = Component<T>;
+impl<'a, R: Renderer, P: 'a + Deref<Target = Element<R>>, T> Deref for MountedComponent<'a, P, T> {
+    type Target = Component<T, R>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
         unsafe {
-            let inner = &*self.handle.parent.inner.get();
-            match inner.children.get(self.handle.idx) {
+            match self.handle.child() {
                 Some(super::element::Child::Component(x)) => {
-                    return &*(x.as_ref().get_ref() as *const Component<dyn Any>
-                        as *const Component<T>);
+                    return &*(x.as_ref().get_ref() as *const Component<dyn Any, R>
+                        as *const Component<T, R>);
                 }
-                _ => unreachable_unchecked(),
+                Some(_) => unreachable_unchecked(),
+                None => panic!("component was removed from its parent"),
             }
         }
     }
@@ -0,0 +1,229 @@
+use super::{context::{current_scope, with_scope}, element::{Child, Content, Element, Inner}, renderer::Renderer};
+use crate::state::Readable;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+/// Child rendered by [`bind_switch`](Element::bind_switch)
+struct Region<K, R: Renderer> {
+    /// Key of the rendered branch
+    key: Option<K>,
+    /// Index of the rendered child in the parent, if any
+    idx: Option<usize>,
+    /// Marks the position of the region inside of the parent, once the first branch is rendered
+    anchor: Option<R::Node>,
+}
+
+/// Renders `then` while `when` is `true`, and `otherwise` while it's `false`.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::{dom::{Element, Show}, state::Writeable};
+///
+/// let visible = Writeable::new(true);
+/// let element = Element::new("div");
+/// let element = element.append_child_inner(
+///     Show::new(&visible, || Element::new("p")).otherwise(|| Element::new("span"))
+/// ).unwrap();
+/// ```
+pub struct Show<'a, F, G> {
+    when: &'a Readable<bool>,
+    then: F,
+    otherwise: G,
+}
+
+/// Renders the branch returned by `render`, rendering it again whenever the key of the state changes.
+pub struct Switch<'a, T, K, R> {
+    state: &'a Readable<T>,
+    key: K,
+    render: R,
+}
+
+impl<'a, F, R: Renderer> Show<'a, F, fn() -> Option<Element<R>>> {
+    #[inline]
+    pub fn new<A: Into<Child<R>>> (when: &'a Readable<bool>, then: F) -> Self where F: FnMut() -> A {
+        return Self {
+            when,
+            then,
+            otherwise: none,
+        };
+    }
+
+    /// Renders `otherwise` while the condition is `false`.
+    #[inline]
+    pub fn otherwise<B: Into<Child<R>>, G: FnMut() -> B> (self, mut otherwise: G) -> Show<'a, F, impl FnMut() -> Option<B>> {
+        return Show {
+            when: self.when,
+            then: self.then,
+            otherwise: move || Some(otherwise()),
+        };
+    }
+}
+
+impl<'a, T, KF, RF> Switch<'a, T, KF, RF> {
+    #[inline]
+    pub fn new<K, C> (state: &'a Readable<T>, key: KF, render: RF) -> Self
+    where
+        KF: FnMut(&T) -> K,
+        RF: FnMut(&T) -> Option<C>,
+    {
+        return Self { state, key, render };
+    }
+}
+
+impl<R: Renderer> Element<R> {
+    /// Renders `then` while `cond` is `true`, and `otherwise` while it's `false`.
+    #[inline]
+    pub fn bind_if<A, B> (
+        &self,
+        cond: &Readable<bool>,
+        mut then: impl 'static + FnMut() -> A,
+        mut otherwise: impl 'static + FnMut() -> B,
+    ) -> Result<(), JsValue>
+    where
+        A: Into<Child<R>>,
+        B: Into<Child<R>>,
+    {
+        self.bind_switch(cond, |x| *x, move |x| match x {
+            true => Some(then().into()),
+            false => Some(otherwise().into()),
+        })
+    }
+
+    /// Renders `then` only while `cond` is `true`.
+    #[inline]
+    pub fn bind_show<A: Into<Child<R>>> (&self, cond: &Readable<bool>, mut then: impl 'static + FnMut() -> A) -> Result<(), JsValue> {
+        self.bind_switch(cond, |x| *x, move |x| x.then(&mut then))
+    }
+
+    /// Renders the child returned by `render`, if any.
+    ///
+    /// Whenever `state` changes, the previous child is dropped and a new one is rendered, but only if the
+    /// key returned by `key` has changed (e.g. the index of a `match` arm).
+    pub fn bind_switch<T, K, C, KF, RF> (
+        &self,
+        state: &Readable<T>,
+        mut key: KF,
+        mut render: RF,
+    ) -> Result<(), JsValue>
+    where
+        T: 'static,
+        K: 'static + PartialEq,
+        C: Into<Child<R>>,
+        KF: 'static + FnMut(&T) -> K,
+        RF: 'static + FnMut(&T) -> Option<C>,
+    {
+        let inner = unsafe { &mut *self.inner.get() };
+
+        // The anchor follows the branch, so it's adopted after it while hydrating
+        let mut region = Region {
+            key: None,
+            idx: None,
+            anchor: None,
+        };
+        state.with(|x| region.update(inner, x, &mut key, &mut render))?;
+
+        let anchor = R::create_anchor();
+        R::append_child(inner.element.as_ref(), &anchor)?;
+        region.anchor = Some(anchor);
+
+        let parent = Rc::downgrade(&self.inner);
+        let scope = current_scope();
+        let sub = state.subscribe(move |x| {
+            if let Some(parent) = parent.upgrade() {
+                let parent = unsafe { &mut *parent.get() };
+                let result = with_scope(scope.clone(), || region.update(parent, x, &mut key, &mut render));
+                if let Err(e) = result {
+                    crate::eprintln!(&e)
+                }
+            }
+        });
+
+        inner.content.push(sub);
+        return Ok(());
+    }
+}
+
+impl<K: PartialEq, R: Renderer> Region<K, R> {
+    fn update<T, C: Into<Child<R>>> (
+        &mut self,
+        parent: &mut Inner<R>,
+        state: &T,
+        key: &mut impl FnMut(&T) -> K,
+        render: &mut impl FnMut(&T) -> Option<C>,
+    ) -> Result<(), JsValue> {
+        let key = key(state);
+        if self.key.as_ref() == Some(&key) {
+            return Ok(());
+        }
+        self.key = Some(key);
+
+        if let Some(idx) = self.idx.take() {
+            let child = parent.children.remove(idx);
+            R::remove_child(parent.element.as_ref(), child.node())?;
+        }
+
+        if let Some(child) = render(state) {
+            let child: Child<R> = child.into();
+            match self.anchor {
+                Some(ref anchor) => R::insert_before(parent.element.as_ref(), child.node(), Some(anchor))?,
+                None => R::append_child(parent.element.as_ref(), child.node())?,
+            }
+            let idx = parent.children.insert(child);
+            parent.attach(idx);
+            self.idx = Some(idx);
+        }
+
+        return Ok(());
+    }
+}
+
+impl<'a, A, B, F, G, R> Content<R> for Show<'a, F, G>
+where
+    A: Into<Child<R>>,
+    B: Into<Child<R>>,
+    R: Renderer,
+    F: 'static + FnMut() -> A,
+    G: 'static + FnMut() -> Option<B>,
+{
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        let Self { when, mut then, mut otherwise } = self;
+        parent.bind_switch(when, |x| *x, move |x| match x {
+            true => Some(then().into()),
+            false => otherwise().map(Into::into),
+        })
+    }
+}
+
+impl<'a, T, K, C, KF, RF, R> Content<R> for Switch<'a, T, KF, RF>
+where
+    T: 'static,
+    K: 'static + PartialEq,
+    C: Into<Child<R>>,
+    R: Renderer,
+    KF: 'static + FnMut(&T) -> K,
+    RF: 'static + FnMut(&T) -> Option<C>,
+{
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.bind_switch(self.state, self.key, self.render)
+    }
+}
+
+/// Converts a branch rendered by [`client!`](crate::client) into a child, logging its error if it failed.
+#[doc(hidden)]
+#[inline]
+pub fn render_branch<C: Into<Child<R>>, R: Renderer> (branch: Result<C, JsValue>) -> Option<Child<R>> {
+    match branch {
+        Ok(x) => Some(x.into()),
+        Err(e) => {
+            crate::eprintln!(&e);
+            None
+        }
+    }
+}
+
+#[inline]
+fn none<R: Renderer> () -> Option<Element<R>> {
+    None
+}
@@ -0,0 +1,108 @@
+use super::{Children, Component, Element, Renderer};
+use std::{
+    any::{Any, TypeId},
+    cell::UnsafeCell,
+    rc::Rc,
+};
+use wasm_bindgen::JsValue;
+
+thread_local! {
+    static SCOPE: UnsafeCell<Rc<Scope>> = UnsafeCell::new(Rc::new(Scope::default()));
+}
+
+/// Values provided by a component, alongside the scope of its parent
+#[derive(Default)]
+pub(super) struct Scope {
+    parent: Option<Rc<Scope>>,
+    values: UnsafeCell<Vec<(TypeId, Rc<dyn Any>)>>,
+}
+
+/// Provides `value` to the component currently being rendered and its descendants, shadowing any value of
+/// the same type provided by its ancestors.
+///
+/// Outside of a component, the value is provided to the whole application.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::{component, client, dom::{Element, provide_context, use_context}};
+/// use wasm_bindgen::JsValue;
+///
+/// struct Theme { color: &'static str }
+///
+/// #[component]
+/// fn App() -> Result<Element, JsValue> {
+///     provide_context(Theme { color: "rebeccapurple" });
+///     client! { <div><Label /></div> }
+/// }
+///
+/// #[component]
+/// fn Label() -> Result<Element, JsValue> {
+///     let theme = use_context::<Theme>().unwrap();
+///     client! { <span style={theme.color}>{"Hello"}</span> }
+/// }
+/// ```
+pub fn provide_context<T: 'static> (value: T) {
+    let scope = current_scope();
+    let values = unsafe { &mut *scope.values.get() };
+    let value = Rc::new(value) as Rc<dyn Any>;
+
+    match values.iter_mut().find(|(id, _)| *id == TypeId::of::<T>()) {
+        Some((_, prev)) => *prev = value,
+        None => values.push((TypeId::of::<T>(), value)),
+    }
+}
+
+/// Returns the value of type `T` provided by the closest ancestor of the component currently being rendered,
+/// if any.
+///
+/// Lookups only work while rendering, so values needed afterwards (e.g. inside of event listeners) must be
+/// retrieved beforehand.
+pub fn use_context<T: 'static> () -> Option<Rc<T>> {
+    let mut scope = Some(current_scope());
+    while let Some(current) = scope {
+        let values = unsafe { &*current.values.get() };
+        if let Some((_, value)) = values.iter().find(|(id, _)| *id == TypeId::of::<T>()) {
+            return value.clone().downcast::<T>().ok();
+        }
+        scope = current.parent.clone();
+    }
+    return None;
+}
+
+/// Renders the component `C` inside of a new context scope.
+///
+/// This is what capitalised tags inside of [`client!`](crate::client) render through.
+#[inline]
+pub fn render<C: ?Sized + Component<R>, R: Renderer> (props: C::Props, children: Children<'_, R>) -> Result<Element<R>, JsValue> {
+    let scope = Rc::new(Scope {
+        parent: Some(current_scope()),
+        values: UnsafeCell::new(Vec::new()),
+    });
+    with_scope(scope, || C::render(props, children))
+}
+
+/// Returns the scope of the component currently being rendered.
+#[inline]
+pub(super) fn current_scope () -> Rc<Scope> {
+    SCOPE.with(|x| unsafe { &*x.get() }.clone())
+}
+
+/// Runs `f` inside of `scope`, so that reactive regions rendered later on see the same contexts.
+#[inline]
+pub(super) fn with_scope<U> (scope: Rc<Scope>, f: impl FnOnce() -> U) -> U {
+    let prev = SCOPE.with(|x| core::mem::replace(unsafe { &mut *x.get() }, scope));
+    let _guard = ScopeGuard(Some(prev));
+    return f();
+}
+
+/// Restores the previous scope once dropped (even if the render panicked)
+struct ScopeGuard(Option<Rc<Scope>>);
+
+impl Drop for ScopeGuard {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(prev) = self.0.take() {
+            SCOPE.with(|x| unsafe { *x.get() = prev });
+        }
+    }
+}
@@ -1,131 +1,155 @@
-use js_sys::Function;
 use slab::Slab;
-use std::{cell::UnsafeCell, ops::Deref, any::Any, pin::Pin, rc::Rc};
-use wasm_bindgen::{prelude::{wasm_bindgen, Closure}, JsCast, JsValue};
-use crate::state::Readable;
+use std::{cell::UnsafeCell, ops::Deref, any::Any, pin::Pin, rc::{Rc, Weak}};
+use wasm_bindgen::JsValue;
+use crate::state::{Readable, Subscription, Writeable};
 
-use super::component::{Component, MountedComponent};
+use super::{component::{Component, MountedComponent}, portal::Portal, attribute::IntoAttribute, event::{Event, EventHandler, EventType, FromEvent, ListenerOptions}, lifecycle::Hooks, renderer::{DefaultRenderer, Renderer}};
+pub use super::backend::{Document, EventTarget, HtmlElement, Node, Text};
+pub(super) use super::backend::Comment;
+#[cfg(target_family = "wasm")]
+pub use super::backend::{Window, window};
 
 thread_local! {
-    pub static DOCUMENT: Document = window().document();
-    pub static BODY: Rc<Element> = Rc::new(Element {
-        inner: UnsafeCell::new(Inner {
-            element: DOCUMENT.with(Document::body),
-            children: Slab::new(),
-            listeners: Slab::new(),
-            texts: Slab::new()
-        })
-    });
-}
-
-#[wasm_bindgen]
-extern "C" {
-    pub type Window;
-    pub type Document;
-    
-    #[wasm_bindgen(extends = Node, js_name = HTMLElement)]
-    pub(super) type HtmlElement;
-
-    #[derive(Clone)]
-    #[wasm_bindgen(extends = Node)]
-    type Text;
-
-    #[derive(Clone)]
-    #[wasm_bindgen(extends = EventTarget)]
-    pub(super) type Node;
-
-    #[derive(Clone)]
-    pub(super) type EventTarget;
-
-    #[wasm_bindgen(structural, method, getter)]
-    fn document(this: &Window) -> Document;
-
-    #[wasm_bindgen(structural, method, getter)]
-    fn body(this: &Document) -> HtmlElement;
-    #[wasm_bindgen(structural, method, js_name = createElement)]
-    fn create_element(this: &Document, tag: &str) -> HtmlElement;
-
-    #[wasm_bindgen(structural, method, catch, js_name = appendChild)]
-    fn append_child (this: &Node, child: &Node) -> Result<Node, JsValue>;
-    #[wasm_bindgen(structural, method, catch, js_name = removeChild)]
-    fn remove_child (this: &Node, child: &Node) -> Result<Node, JsValue>;
-
-    #[wasm_bindgen(constructor)]
-    fn new (s: &str) -> Text;
-    #[wasm_bindgen(structural, method, getter)]
-    fn data (this: &Text) -> String;
-    #[wasm_bindgen(structural, method, setter, js_name = data)]
-    fn set_data (this: &Text, s: &str);
-
-    #[wasm_bindgen(structural, method, js_name = addEventListener)]
-    fn add_event_listener (this: &EventTarget, event: &str, f: &Function);
-    #[wasm_bindgen(structural, method, js_name = removeEventListener)]
-    fn remove_event_listener (this: &EventTarget, event: &str, f: &Function);
+    pub static DOCUMENT: Document = super::backend::document();
+    pub static BODY: Rc<Element> = Rc::new(Element::from_existing(DOCUMENT.with(Document::body)));
 }
 
 #[doc(hidden)]
-pub enum Child {
-    Element (Element),
-    Component (Pin<Box<Component<dyn Any>>>)
+pub enum Child<R: Renderer = DefaultRenderer> {
+    Element (Element<R>),
+    Component (Pin<Box<Component<dyn Any, R>>>),
+    Portal (Portal<R>),
 }
 
-enum InnerText {
-    Owned (Box<Readable<dyn AsRef<str>>>),
-    Shared (Rc<Readable<dyn AsRef<str>>>)
+pub(super) struct Inner<R: Renderer> {
+    pub(super) element: R::Element,
+    pub(super) children: Slab<Child<R>>,
+    /// Incremented whenever the children are cleared, so that the handles to them can be told apart from new ones
+    pub(super) generation: usize,
+    /// Event name, whether it was registered for the capture phase, and handle of every listener
+    pub(super) listeners: Slab<(&'static str, bool, R::Listener)>,
+    /// Bindings of the element itself (e.g. attributes)
+    pub(super) subscriptions: Vec<Subscription>,
+    /// Bindings of the element's content (e.g. text), dropped alongside its children
+    pub(super) content: Vec<Subscription>,
+    pub(super) hooks: Hooks,
+    /// Whether the element is attached to the document
+    pub(super) mounted: bool,
 }
 
-pub(super) struct Inner {
-    pub(super) element: HtmlElement,
-    pub(super) children: Slab<Child>,
-    pub(super) listeners: Slab<(&'static str, Closure<dyn FnMut()>)>,
-    pub(super) texts: Slab<InnerText>
-}
-
-pub struct Element {
-    pub(super) inner: UnsafeCell<Inner>,
+/// An element rendered by the renderer `R`, alongside its children, listeners, bindings and hooks.
+pub struct Element<R: Renderer = DefaultRenderer> {
+    pub(super) inner: Rc<UnsafeCell<Inner<R>>>,
 }
 
 pub struct MountedElement<P> {
     pub(super) parent: P,
     pub(super) idx: usize,
+    pub(super) generation: usize,
+}
+
+/// A handle to an event listener, borrowed from its element.
+pub struct ListenerRef<'a, R: Renderer = DefaultRenderer> {
+    parent: &'a Element<R>,
+    idx: usize,
 }
 
-pub struct ListenerRef<'a> {
-    parent: &'a Element,
+/// An owned handle to an event listener, which can outlive the borrow of its element.
+///
+/// The listener isn't removed when the handle is dropped.
+pub struct Listener<R: Renderer = DefaultRenderer> {
+    parent: Weak<UnsafeCell<Inner<R>>>,
     idx: usize,
 }
 
 impl Element {
+    /// Creates an element with the specified tag, rendered by the [default renderer](DefaultRenderer).
+    #[inline]
+    pub fn new (tag: &str) -> Self {
+        Self::create(tag)
+    }
+
+    /// Wraps an element that already exists in the document (e.g. one rendered by the server).
+    ///
+    /// The current contents of the element are left untouched.
     #[inline]
-    pub fn new(tag: &str) -> Self {
+    pub fn from_existing (element: HtmlElement) -> Self {
+        Self::wrap(element)
+    }
+
+    /// Serialises the element and its descendants into HTML.
+    ///
+    /// Reactive regions (e.g. lists) are followed by an empty comment, which marks their position when the
+    /// markup is hydrated. Outside of WebAssembly, properties that reflect the state of form controls (e.g.
+    /// `value` or `checked`) are serialised as attributes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spiderweb::client;
+    ///
+    /// let element = client! { <p class={"greeting"}>{"Fish & chips"}</p> }.unwrap();
+    /// assert_eq!(element.to_html(), r#"<p class="greeting">Fish &amp; chips</p>"#);
+    /// ```
+    #[inline]
+    pub fn to_html (&self) -> String {
+        self.html_node().outer_html()
+    }
+}
+
+impl<R: Renderer> Element<R> {
+    /// Creates an element with the specified tag, rendered by `R`.
+    #[inline]
+    pub fn create (tag: &str) -> Self {
+        return Self::from_node(R::create_element(tag), false);
+    }
+
+    /// Wraps an element that has already been rendered, leaving its current contents untouched.
+    #[inline]
+    pub(super) fn wrap (element: R::Element) -> Self {
+        let mounted = R::is_connected(element.as_ref());
+        return Self::from_node(element, mounted);
+    }
+
+    #[inline]
+    fn from_node (element: R::Element, mounted: bool) -> Self {
         let inner = Inner {
-            element: DOCUMENT.with(|doc| doc.create_element(tag)),
+            element,
             children: Slab::new(),
+            generation: 0,
             listeners: Slab::new(),
-            texts: Slab::new(),
+            subscriptions: Vec::new(),
+            content: Vec::new(),
+            hooks: Hooks::default(),
+            mounted,
         };
 
         return Self {
-            inner: UnsafeCell::new(inner),
+            inner: Rc::new(UnsafeCell::new(inner)),
         };
     }
 
+    /// Returns the underlying node of the element.
+    #[inline]
+    pub fn html_node (&self) -> &R::Element {
+        &unsafe { &*self.inner.get() }.element
+    }
+
     #[inline]
     pub fn add_text (&self, s: &str) -> Result<(), JsValue> {
-        let text: Text = Text::new(s);
-        unsafe { &*self.inner.get() }.element.append_child(&text)?;
-        return Ok(())
+        let text = R::create_text(s);
+        R::append_child(self.html_node().as_ref(), text.as_ref())
     }
 
     #[inline]
-    pub fn bind_text<T: AsRef<str>> (&self, state: Readable<T>) -> Result<(), JsValue> {
-        let state = &state as &Readable<dyn AsRef<str>>;
-        let text: Text = state.with(|x| Text::new(x.as_ref()));
+    pub fn bind_text<T: ?Sized + 'static + AsRef<str>> (&self, state: &Readable<T>) -> Result<(), JsValue> {
+        let text = state.with(|x| R::create_text(x.as_ref()));
 
         let my_text = text.clone();
-        state.subscribe(move |x| my_text.set_data(x.as_ref())); // this currently leaks!
+        let sub = state.subscribe(move |x| R::set_text(&my_text, x.as_ref()));
 
-        unsafe { &*self.inner.get() }.element.append_child(&text)?;
+        let inner = unsafe { &mut *self.inner.get() };
+        R::append_child(inner.element.as_ref(), text.as_ref())?;
+        inner.content.push(sub);
         return Ok(())
     }
 
@@ -141,44 +165,113 @@ impl Element {
 
     #[inline]
     pub fn create_component_by_deref<'a, D: 'a + Deref<Target = Self>, T: Any> (this: D, tag: &str, state: T) -> Result<Pin<MountedComponent<'a, D, T>>, JsValue> {
-        let comp = Component::new(tag, state);
+        let comp = Component::<T, R>::new(tag, state);
         let inner = Self::append_child_by_deref(this, comp)?;
         return unsafe { Ok(Pin::new_unchecked(MountedComponent { handle: inner, _phtm: std::marker::PhantomData })) }
     }
 
     #[inline]
-    pub fn append_child (&self, element: impl Into<Child>) -> Result<MountedElement<&Self>, JsValue> {
+    pub fn append_child (&self, element: impl Into<Child<R>>) -> Result<MountedElement<&Self>, JsValue> {
         Self::append_child_by_deref(self, element)
     }
 
     #[inline]
-    pub fn append_child_shared (self: Rc<Self>, element: impl Into<Child>) -> Result<MountedElement<Rc<Self>>, JsValue> {
+    pub fn append_child_shared (self: Rc<Self>, element: impl Into<Child<R>>) -> Result<MountedElement<Rc<Self>>, JsValue> {
         Self::append_child_by_deref(self, element)
     }
 
     #[inline]
-    pub fn append_child_by_deref<D: Deref<Target = Self>> (this: D, element: impl Into<Child>) -> Result<MountedElement<D>, JsValue> {
+    pub fn append_child_by_deref<D: Deref<Target = Self>> (this: D, element: impl Into<Child<R>>) -> Result<MountedElement<D>, JsValue> {
         let inner = unsafe { &mut *this.inner.get() };
-        let element: Child = element.into();
-        inner.element.append_child(element.html_element())?;
+        let element: Child<R> = element.into();
+        R::append_child(inner.element.as_ref(), element.node())?;
 
         let idx = inner
             .children
             .insert(element);
-        return Ok(MountedElement { parent: this, idx });
+        inner.attach(idx);
+
+        let generation = inner.generation;
+        return Ok(MountedElement { parent: this, idx, generation });
+    }
+
+    /// Removes every child of the element, dropping them alongside their listeners, bindings and hooks.
+    ///
+    /// Previously mounted handles to the children are invalidated, and return an error if they're used afterwards.
+    pub fn clear (&self) -> Result<(), JsValue> {
+        let inner = unsafe { &mut *self.inner.get() };
+        inner.generation += 1;
+        inner.children.clear();
+        inner.content.clear();
+        R::clear(inner.element.as_ref());
+        return Ok(())
+    }
+
+    /// Creates a new element, to be filled by the builder methods used by [`client!`](crate::client)
+    #[doc(hidden)]
+    #[inline]
+    pub fn stateless (tag: &str) -> Self {
+        Self::create(tag)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn append_child_inner<C: Content<R>> (self, content: C) -> Result<Self, JsValue> {
+        content.append_to(&self)?;
+        return Ok(self)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_attribute_inner<A: IntoAttribute> (self, name: &str, value: A) -> Result<Self, JsValue> {
+        value.set_to(&self, name)?;
+        return Ok(self)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn bind_inner<F: FnOnce(&Self) -> Result<(), JsValue>> (self, f: F) -> Result<Self, JsValue> {
+        f(&self)?;
+        return Ok(self)
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn set_callback_inner<Args, F: EventHandler<Args>> (self, event: &'static str, f: F) -> Self {
+        let _handle = self.add_event_listener_boxed(event, f.into_listener());
+        return self
+    }
+
+    /// Adds a listener for events of type `E`, which receives the event's payload.
+    #[inline]
+    pub fn add_event_listener<E: EventType>(&self, mut f: impl 'static + FnMut(E::Event)) -> ListenerRef<'_, R> {
+        self.add_event_listener_boxed(E::NAME, Box::new(move |ev: Event| f(E::Event::from_event(ev))))
+    }
+
+    /// Adds a listener for events of type `E`, registered with the specified options.
+    #[inline]
+    pub fn add_event_listener_with_options<E: EventType>(&self, options: &ListenerOptions, mut f: impl 'static + FnMut(E::Event)) -> ListenerRef<'_, R> {
+        self.add_event_listener_boxed_with_options(E::NAME, options, Box::new(move |ev: Event| f(E::Event::from_event(ev))))
     }
 
     #[inline]
-    pub fn add_event_listener<'a>(
+    pub fn add_event_listener_boxed<'a>(
         &'a self,
         event: &'static str,
-        f: Box<dyn FnMut()>,
-    ) -> ListenerRef<'a> {
-        let this = unsafe { &mut *self.inner.get() };
-        let f = Closure::wrap(f);
+        f: Box<dyn FnMut(Event)>,
+    ) -> ListenerRef<'a, R> {
+        self.add_event_listener_boxed_with_options(event, &ListenerOptions::default(), f)
+    }
 
-        this.element.add_event_listener(event, f.as_ref().unchecked_ref());
-        let idx = this.listeners.insert((event, f));
+    pub fn add_event_listener_boxed_with_options<'a>(
+        &'a self,
+        event: &'static str,
+        options: &ListenerOptions,
+        f: Box<dyn FnMut(Event)>,
+    ) -> ListenerRef<'a, R> {
+        let this = unsafe { &mut *self.inner.get() };
+        let f = R::add_listener(&this.element, event, f, options);
+        let idx = this.listeners.insert((event, options.capture, f));
         
         return ListenerRef {
             parent: self,
@@ -187,81 +280,262 @@ impl Element {
     }
 }
 
-impl<P: Deref<Target = Element>> Deref for MountedElement<P> {
-    type Target = Element;
+impl<R: Renderer, P: Deref<Target = Element<R>>> Deref for MountedElement<P> {
+    type Target = Element<R>;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        unsafe {
-            return (&*self.parent.inner.get())
-                .children
-                .get(self.idx)
-                .unwrap_unchecked()
-                .element();
+        return self.child().expect("element was removed from its parent").element();
+    }
+}
+
+impl<R: Renderer, P: Deref<Target = Element<R>>> MountedElement<P> {
+    /// Removes the element from its parent, dropping it alongside its listeners and bindings.
+    #[inline]
+    pub fn remove (self) -> Result<(), JsValue> {
+        self.detach().map(drop)
+    }
+
+    /// Replaces the element with `element`, returning a handle to the new one.
+    pub fn replace_with (self, element: impl Into<Child<R>>) -> Result<MountedElement<P>, JsValue> {
+        let inner = self.parent_inner()?;
+        let element: Child<R> = element.into();
+        
+        let prev = &mut inner.children[self.idx];
+        R::replace_child(inner.element.as_ref(), element.node(), prev.node())?;
+        drop(core::mem::replace(prev, element));
+        inner.attach(self.idx);
+        return Ok(self)
+    }
+
+    /// Inserts `element` into the parent, right before this element.
+    pub fn insert_before (&self, element: impl Into<Child<R>>) -> Result<MountedElement<&Element<R>>, JsValue> {
+        let inner = self.parent_inner()?;
+        let element: Child<R> = element.into();
+        R::insert_before(inner.element.as_ref(), element.node(), Some(inner.children[self.idx].node()))?;
+
+        let idx = inner.children.insert(element);
+        inner.attach(idx);
+        return Ok(MountedElement { parent: &self.parent, idx, generation: self.generation })
+    }
+
+    /// Moves the element to the end of `parent`, preserving its state, listeners and bindings.
+    #[inline]
+    pub fn move_to<D: Deref<Target = Element<R>>> (self, parent: D) -> Result<MountedElement<D>, JsValue> {
+        let child = self.detach()?;
+        Element::append_child_by_deref(parent, child)
+    }
+
+    #[inline]
+    fn detach (self) -> Result<Child<R>, JsValue> {
+        let inner = self.parent_inner()?;
+        R::remove_child(inner.element.as_ref(), inner.children[self.idx].node())?;
+        
+        let child = inner.children.remove(self.idx);
+        child.unmount();
+        return Ok(child)
+    }
+
+    /// Returns the child this handle points to, or `None` if it was removed by [`Element::clear`].
+    #[inline]
+    pub(super) fn child (&self) -> Option<&Child<R>> {
+        let inner = unsafe { &*self.parent.inner.get() };
+        if inner.generation != self.generation {
+            return None
+        }
+        return inner.children.get(self.idx)
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    fn parent_inner (&self) -> Result<&mut Inner<R>, JsValue> {
+        if self.child().is_none() {
+            return Err(crate::error_value("element was removed from its parent"))
+        }
+        return Ok(unsafe { &mut *self.parent.inner.get() })
+    }
+}
+
+impl<'a, R: Renderer> ListenerRef<'a, R> {
+    /// Removes the listener from its element.
+    #[inline]
+    pub fn remove (self) {
+        remove_listener(unsafe { &mut *self.parent.inner.get() }, self.idx)
+    }
+
+    /// Converts the handle into a `'static` one, which can be stored (e.g. inside of a component's state).
+    #[inline]
+    pub fn into_owned (self) -> Listener<R> {
+        return Listener {
+            parent: Rc::downgrade(&self.parent.inner),
+            idx: self.idx,
+        }
+    }
+}
+
+impl<R: Renderer> Listener<R> {
+    /// Removes the listener from its element. If the element has already been dropped, this is a no-op.
+    #[inline]
+    pub fn remove (self) {
+        if let Some(parent) = self.parent.upgrade() {
+            remove_listener(unsafe { &mut *parent.get() }, self.idx)
         }
     }
 }
 
-impl Drop for Element {
+#[inline]
+fn remove_listener<R: Renderer> (inner: &mut Inner<R>, idx: usize) {
+    if let Some((event, capture, f)) = inner.listeners.try_remove(idx) {
+        R::remove_listener(&inner.element, event, f, capture);
+    }
+}
+
+impl<R: Renderer> Drop for Inner<R> {
     #[inline]
     fn drop(&mut self) {
-        let inner = self.inner.get_mut();
-        for (event, f) in inner.listeners.drain() {
-            inner.element.remove_event_listener(event, f.as_ref().unchecked_ref());
+        for (event, capture, f) in self.listeners.drain() {
+            R::remove_listener(&self.element, event, f, capture);
         }
+
+        self.unmount();
+        self.cleanup();
     }
 }
 
-impl Child {
+impl<R: Renderer> Child<R> {
     #[inline]
-    fn element (&self) -> &Element {
+    pub(super) fn element (&self) -> &Element<R> {
         match self {
             Self::Element(x) => x,
-            Self::Component(x) => &x.element
+            Self::Component(x) => &x.element,
+            Self::Portal(x) => &x.content,
+        }
+    }
+
+    /// Returns the node placed inside of the parent
+    #[inline]
+    pub(super) fn node (&self) -> &R::Node {
+        match self {
+            Self::Portal(x) => &x.anchor,
+            _ => self.element().html_node().as_ref()
         }
     }
+}
+
+/* CONTENT */
+/// A value that can be placed inside of an [`Element`] by the [`client!`](crate::client) macro.
+pub trait Content<R: Renderer = DefaultRenderer> {
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue>;
+}
+
+impl<R: Renderer> Content<R> for Element<R> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.append_child(self).map(drop)
+    }
+}
+
+impl<R: Renderer> Content<R> for Portal<R> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.append_child(self).map(drop)
+    }
+}
+
+impl<T: Any, R: Renderer> Content<R> for Component<T, R> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.append_child(self).map(drop)
+    }
+}
+
+impl<R: Renderer> Content<R> for &str {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.add_text(self)
+    }
+}
+
+impl<R: Renderer> Content<R> for String {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.add_text(&self)
+    }
+}
+
+impl<R: Renderer> Content<R> for &String {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.add_text(self)
+    }
+}
+
+impl<T: ?Sized + 'static + AsRef<str>, R: Renderer> Content<R> for &Readable<T> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.bind_text(self)
+    }
+}
+
+impl<T: ?Sized + 'static + AsRef<str>, R: Renderer> Content<R> for &Writeable<T> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.bind_text(self)
+    }
+}
+
+impl<T: ?Sized + 'static + AsRef<str>, R: Renderer> Content<R> for &Rc<Readable<T>> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.bind_text(self)
+    }
+}
 
+impl<T: ?Sized + 'static + AsRef<str>, R: Renderer> Content<R> for &Rc<Writeable<T>> {
     #[inline]
-    fn html_element (&self) -> &HtmlElement {
-        &unsafe { &*self.element().inner.get() }.element
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.bind_text(self)
     }
 }
 
 /* CHILD */
-impl From<Element> for Child {
+impl<R: Renderer> From<Element<R>> for Child<R> {
     #[inline]
-    fn from(value: Element) -> Self {
+    fn from(value: Element<R>) -> Self {
+        R::finish(value.html_node());
         Self::Element(value)
     }
 }
 
-impl<T: Any> From<Component<T>> for Child {
+impl<R: Renderer> From<Portal<R>> for Child<R> {
+    #[inline]
+    fn from(value: Portal<R>) -> Self {
+        Self::Portal(value)
+    }
+}
+
+impl<T: Any, R: Renderer> From<Component<T, R>> for Child<R> {
     #[inline]
-    fn from(value: Component<T>) -> Self {
+    fn from(value: Component<T, R>) -> Self {
+        R::finish(value.element.html_node());
         Self::Component(Box::pin(value))
     }
 }
 
-impl From<Box<Component<dyn Any>>> for Child {
+impl<R: Renderer> From<Box<Component<dyn Any, R>>> for Child<R> {
     #[inline]
-    fn from(value: Box<Component<dyn Any>>) -> Self {
+    fn from(value: Box<Component<dyn Any, R>>) -> Self {
         Self::Component(Box::into_pin(value))
     }
 }
 
-impl From<Pin<Box<Component<dyn Any>>>> for Child {
+impl<R: Renderer> From<Pin<Box<Component<dyn Any, R>>>> for Child<R> {
     #[inline]
-    fn from(value: Pin<Box<Component<dyn Any>>>) -> Self {
+    fn from(value: Pin<Box<Component<dyn Any, R>>>) -> Self {
         Self::Component(value)
     }
 }
 
-#[inline]
-pub fn window () -> Window {
-    use crate::wasm_bindgen::UnwrapThrowExt;
-    JsCast::dyn_into(js_sys::global()).unwrap_throw()
-}
-
 #[inline]
 pub fn body () -> Rc<Element> {
     BODY.with(Clone::clone)
@@ -0,0 +1,323 @@
+use crate::sync::abort::AbortSignal;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+#[cfg(not(target_family = "wasm"))]
+pub use super::memory::event::*;
+
+#[cfg(target_family = "wasm")]
+#[wasm_bindgen]
+extern "C" {
+    /// A DOM event
+    #[derive(Debug, Clone, PartialEq)]
+    pub type Event;
+
+    #[wasm_bindgen(structural, method, getter, js_name = type)]
+    pub fn event_type (this: &Event) -> String;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn target (this: &Event) -> JsValue;
+    #[wasm_bindgen(structural, method, getter, js_name = currentTarget)]
+    pub fn current_target (this: &Event) -> JsValue;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn bubbles (this: &Event) -> bool;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn cancelable (this: &Event) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = defaultPrevented)]
+    pub fn default_prevented (this: &Event) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = isTrusted)]
+    pub fn is_trusted (this: &Event) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = timeStamp)]
+    pub fn time_stamp (this: &Event) -> f64;
+    #[wasm_bindgen(structural, method, js_name = preventDefault)]
+    pub fn prevent_default (this: &Event);
+    #[wasm_bindgen(structural, method, js_name = stopPropagation)]
+    pub fn stop_propagation (this: &Event);
+    #[wasm_bindgen(structural, method, js_name = stopImmediatePropagation)]
+    pub fn stop_immediate_propagation (this: &Event);
+
+    /// An event caused by a user interaction
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = Event, js_name = UIEvent)]
+    pub type UiEvent;
+
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn detail (this: &UiEvent) -> i32;
+
+    /// An event caused by the user interacting with a pointing device (e.g. a mouse)
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = UiEvent, extends = Event)]
+    pub type MouseEvent;
+
+    #[wasm_bindgen(structural, method, getter, js_name = clientX)]
+    pub fn client_x (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = clientY)]
+    pub fn client_y (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = pageX)]
+    pub fn page_x (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = pageY)]
+    pub fn page_y (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = screenX)]
+    pub fn screen_x (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = screenY)]
+    pub fn screen_y (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = offsetX)]
+    pub fn offset_x (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = offsetY)]
+    pub fn offset_y (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = movementX)]
+    pub fn movement_x (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = movementY)]
+    pub fn movement_y (this: &MouseEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn button (this: &MouseEvent) -> i16;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn buttons (this: &MouseEvent) -> u16;
+    #[wasm_bindgen(structural, method, getter, js_name = altKey)]
+    pub fn alt_key (this: &MouseEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = ctrlKey)]
+    pub fn ctrl_key (this: &MouseEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = shiftKey)]
+    pub fn shift_key (this: &MouseEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = metaKey)]
+    pub fn meta_key (this: &MouseEvent) -> bool;
+
+    /// An event caused by the user interacting with the keyboard
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = UiEvent, extends = Event)]
+    pub type KeyboardEvent;
+
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn key (this: &KeyboardEvent) -> String;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn code (this: &KeyboardEvent) -> String;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn location (this: &KeyboardEvent) -> u32;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn repeat (this: &KeyboardEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = isComposing)]
+    pub fn is_composing (this: &KeyboardEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = altKey)]
+    pub fn alt_key (this: &KeyboardEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = ctrlKey)]
+    pub fn ctrl_key (this: &KeyboardEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = shiftKey)]
+    pub fn shift_key (this: &KeyboardEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = metaKey)]
+    pub fn meta_key (this: &KeyboardEvent) -> bool;
+
+    /// An event notifying of changes to editable content
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = UiEvent, extends = Event)]
+    pub type InputEvent;
+
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn data (this: &InputEvent) -> Option<String>;
+    #[wasm_bindgen(structural, method, getter, js_name = inputType)]
+    pub fn input_type (this: &InputEvent) -> String;
+    #[wasm_bindgen(structural, method, getter, js_name = isComposing)]
+    pub fn is_composing (this: &InputEvent) -> bool;
+
+    /// An event related to focus (e.g. `focus` or `blur`)
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = UiEvent, extends = Event)]
+    pub type FocusEvent;
+
+    #[wasm_bindgen(structural, method, getter, js_name = relatedTarget)]
+    pub fn related_target (this: &FocusEvent) -> JsValue;
+
+    /// The event fired when a form is submitted
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = Event)]
+    pub type SubmitEvent;
+
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn submitter (this: &SubmitEvent) -> JsValue;
+
+    /// An event caused by a pointer (e.g. a mouse, a pen or a touch contact)
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = MouseEvent, extends = UiEvent, extends = Event)]
+    pub type PointerEvent;
+
+    #[wasm_bindgen(structural, method, getter, js_name = pointerId)]
+    pub fn pointer_id (this: &PointerEvent) -> i32;
+    #[wasm_bindgen(structural, method, getter, js_name = pointerType)]
+    pub fn pointer_type (this: &PointerEvent) -> String;
+    #[wasm_bindgen(structural, method, getter, js_name = isPrimary)]
+    pub fn is_primary (this: &PointerEvent) -> bool;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn width (this: &PointerEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn height (this: &PointerEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn pressure (this: &PointerEvent) -> f32;
+
+    /// An event caused by the user rotating a mouse wheel (or similar device)
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = MouseEvent, extends = UiEvent, extends = Event)]
+    pub type WheelEvent;
+
+    #[wasm_bindgen(structural, method, getter, js_name = deltaX)]
+    pub fn delta_x (this: &WheelEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = deltaY)]
+    pub fn delta_y (this: &WheelEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = deltaZ)]
+    pub fn delta_z (this: &WheelEvent) -> f64;
+    #[wasm_bindgen(structural, method, getter, js_name = deltaMode)]
+    pub fn delta_mode (this: &WheelEvent) -> u32;
+
+    /// An event caused by a drag and drop interaction
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = MouseEvent, extends = UiEvent, extends = Event)]
+    pub type DragEvent;
+
+    #[wasm_bindgen(structural, method, getter, js_name = dataTransfer)]
+    pub fn data_transfer (this: &DragEvent) -> JsValue;
+}
+
+/// Options with which an event listener is registered.
+#[derive(Debug, Clone, Default)]
+pub struct ListenerOptions {
+    /// Automatically removes the listener after it's invoked once
+    pub once: bool,
+    /// Promises that the listener won't call [`prevent_default`](Event::prevent_default)
+    pub passive: bool,
+    /// Invokes the listener during the capture phase, before the event reaches the target
+    pub capture: bool,
+    /// Removes the listener when the signal is aborted
+    pub signal: Option<AbortSignal>,
+}
+
+/// A type of DOM event, alongside the payload its listeners receive.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::dom::{Element, event::Click};
+///
+/// let button = Element::new("button");
+/// button.add_event_listener::<Click>(|ev| spiderweb::println!("{}, {}", ev.client_x(), ev.client_y()));
+/// ```
+pub trait EventType {
+    /// Name under which the event is dispatched
+    const NAME: &'static str;
+    type Event: FromEvent;
+}
+
+/// A type that an [`Event`] can be converted into, such as [`MouseEvent`].
+///
+/// In WebAssembly, this is every [`JsCast`](wasm_bindgen::JsCast) type, which are converted without any checks.
+pub trait FromEvent {
+    fn from_event (event: Event) -> Self;
+}
+
+/// A closure that can handle events, either ignoring their payload or receiving it as any [`FromEvent`] type.
+pub trait EventHandler<Args> {
+    fn into_listener (self) -> Box<dyn FnMut(Event)>;
+}
+
+impl<F: 'static + FnMut()> EventHandler<()> for F {
+    #[inline]
+    fn into_listener (mut self) -> Box<dyn FnMut(Event)> {
+        Box::new(move |_| self())
+    }
+}
+
+impl<E: FromEvent, F: 'static + FnMut(E)> EventHandler<(E,)> for F {
+    #[inline]
+    fn into_listener (mut self) -> Box<dyn FnMut(Event)> {
+        Box::new(move |ev: Event| self(E::from_event(ev)))
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl<T: JsCast> FromEvent for T {
+    #[inline]
+    fn from_event (event: Event) -> Self {
+        event.unchecked_into()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl InputEvent {
+    /// Returns the `value` of the event's target (e.g. an `<input>` or a `<textarea>`), if it has any.
+    #[inline]
+    pub fn value (&self) -> Option<String> {
+        js_sys::Reflect::get(&self.target(), &JsValue::from_str("value"))
+            .ok()
+            .and_then(|x| x.as_string())
+    }
+}
+
+macro_rules! impl_event_type {
+    ($($event:ty => { $($(#[$meta:meta])* $name:ident = $js:literal),+ $(,)? }),+ $(,)?) => {
+        $(
+            $(
+                $(#[$meta])*
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                pub struct $name;
+
+                impl EventType for $name {
+                    const NAME: &'static str = $js;
+                    type Event = $event;
+                }
+            )+
+        )+
+    };
+}
+
+impl_event_type! {
+    MouseEvent => {
+        Click = "click",
+        DblClick = "dblclick",
+        MouseDown = "mousedown",
+        MouseUp = "mouseup",
+        MouseMove = "mousemove",
+        MouseEnter = "mouseenter",
+        MouseLeave = "mouseleave",
+        MouseOver = "mouseover",
+        MouseOut = "mouseout",
+        ContextMenu = "contextmenu",
+    },
+    KeyboardEvent => {
+        KeyDown = "keydown",
+        KeyUp = "keyup",
+    },
+    InputEvent => {
+        Input = "input",
+        BeforeInput = "beforeinput",
+    },
+    Event => {
+        Change = "change",
+        Reset = "reset",
+        Scroll = "scroll",
+    },
+    FocusEvent => {
+        Focus = "focus",
+        Blur = "blur",
+        FocusIn = "focusin",
+        FocusOut = "focusout",
+    },
+    SubmitEvent => {
+        Submit = "submit",
+    },
+    PointerEvent => {
+        PointerDown = "pointerdown",
+        PointerUp = "pointerup",
+        PointerMove = "pointermove",
+        PointerEnter = "pointerenter",
+        PointerLeave = "pointerleave",
+        PointerOver = "pointerover",
+        PointerOut = "pointerout",
+        PointerCancel = "pointercancel",
+    },
+    WheelEvent => {
+        Wheel = "wheel",
+    },
+    DragEvent => {
+        Drag = "drag",
+        DragStart = "dragstart",
+        DragEnd = "dragend",
+        DragEnter = "dragenter",
+        DragLeave = "dragleave",
+        DragOver = "dragover",
+        Drop = "drop",
+    },
+}
@@ -0,0 +1,231 @@
+use super::element::{Comment, HtmlElement, Node, Text, DOCUMENT};
+use std::cell::UnsafeCell;
+use wasm_bindgen::JsValue;
+
+thread_local! {
+    /// Position of the next node to adopt inside of every element being hydrated
+    static CURSORS: UnsafeCell<Vec<Cursor>> = UnsafeCell::new(Vec::new());
+}
+
+const ELEMENT_NODE: u16 = 1;
+const TEXT_NODE: u16 = 3;
+const COMMENT_NODE: u16 = 8;
+
+struct Cursor {
+    parent: Node,
+    next: Option<Node>,
+    /// Whether the parent was created because of a mismatch, in which case its children are created too
+    created: bool,
+}
+
+/// Runs `f` while adopting the existing children of `target`, instead of creating new nodes.
+///
+/// Nodes left unadopted are removed, and reported as mismatches in debug builds.
+pub(super) fn hydrate_with<U> (target: &Node, f: impl FnOnce() -> U) -> U {
+    push(target.clone(), false);
+    let result = f();
+    finish(target);
+    return result;
+}
+
+/// Returns `true` while hydrating.
+#[inline]
+pub(super) fn is_hydrating () -> bool {
+    CURSORS.with(|x| !unsafe { &*x.get() }.is_empty())
+}
+
+/// Appends `node` to `parent`, unless it was already adopted from it.
+#[inline]
+pub(super) fn append_node (parent: &Node, node: &Node) -> Result<(), JsValue> {
+    if is_hydrating() && node.parent_node().as_ref() == Some(parent) {
+        return Ok(());
+    }
+    parent.append_child(node)?;
+    return Ok(());
+}
+
+/// Adopts the next node as an element with the specified tag, or `None` if not hydrating.
+pub(super) fn element (tag: &str) -> Option<HtmlElement> {
+    let (parent, node) = next(true)?;
+
+    let (element, created) = match node {
+        Some(node) if node.node_type() == ELEMENT_NODE && node.node_name().eq_ignore_ascii_case(tag) => {
+            advance(&node);
+            (node.into_element(), false)
+        }
+        node => {
+            mismatch(&format!("<{tag}>"), node.as_ref());
+            let element = DOCUMENT.with(|doc| doc.create_element(tag));
+            replace(&parent, &element, node.as_ref());
+            (element, true)
+        }
+    };
+
+    push(element.clone().into(), created);
+    return Some(element);
+}
+
+/// Adopts the next node as a text node with the specified contents, or `None` if not hydrating.
+///
+/// Adjacent text nodes are merged by the HTML parser, so they're split back as they're adopted.
+pub(super) fn text (s: &str) -> Option<Text> {
+    let (parent, node) = next(false)?;
+
+    if s.is_empty() {
+        // Empty text nodes aren't preserved by the HTML parser
+        let text = Text::new(s);
+        insert(&parent, &text, node.as_ref());
+        return Some(text);
+    }
+
+    let text = match node {
+        Some(node) if node.node_type() == TEXT_NODE => {
+            let text = node.into_text();
+            let data = text.data();
+
+            if data == s {
+                advance(&text);
+            } else if data.starts_with(s) {
+                let rest = text.split_text(s.encode_utf16().count() as u32);
+                set_next(Some(rest.into()));
+            } else {
+                mismatch(&format!("{s:?}"), Some(&text));
+                text.set_data(s);
+                advance(&text);
+            }
+            text
+        }
+        node => {
+            mismatch(&format!("{s:?}"), node.as_ref());
+            let text = Text::new(s);
+            replace(&parent, &text, node.as_ref());
+            text
+        }
+    };
+
+    return Some(text);
+}
+
+/// Adopts the next node as a comment, used to mark positions inside of the parent, or `None` if not
+/// hydrating.
+pub(super) fn anchor () -> Option<Comment> {
+    let (parent, node) = next(true)?;
+
+    let comment = match node {
+        Some(node) if node.node_type() == COMMENT_NODE => {
+            advance(&node);
+            node.into_comment()
+        }
+        node => {
+            mismatch("<!---->", node.as_ref());
+            let comment = Comment::new("");
+            replace(&parent, &comment, node.as_ref());
+            comment
+        }
+    };
+
+    return Some(comment);
+}
+
+/// Marks the element as completely built, so that the following nodes are adopted from its parent.
+pub(super) fn finish (element: &Node) {
+    CURSORS.with(|x| {
+        let cursors = unsafe { &mut *x.get() };
+        let Some(idx) = cursors.iter().rposition(|x| &x.parent == element) else {
+            return;
+        };
+
+        for mut cursor in cursors.drain(idx..).rev() {
+            while let Some(node) = cursor.next.take() {
+                cursor.next = node.next_sibling();
+                if !is_whitespace(&node) {
+                    mismatch(&format!("end of {}", cursor.parent.node_name()), Some(&node));
+                }
+                if let Err(e) = cursor.parent.remove_child(&node) {
+                    crate::eprintln!(&e)
+                }
+            }
+        }
+    })
+}
+
+#[inline]
+fn push (parent: Node, created: bool) {
+    let next = parent.first_child();
+    CURSORS.with(|x| unsafe { &mut *x.get() }.push(Cursor { parent, next, created }))
+}
+
+/// Returns the parent and next node of the current cursor, or `None` if the parent's children aren't adopted.
+#[inline]
+fn next (skip: bool) -> Option<(Node, Option<Node>)> {
+    CURSORS.with(|x| {
+        let cursor = unsafe { &mut *x.get() }.last_mut().filter(|x| !x.created)?;
+        if skip {
+            skip_whitespace(cursor);
+        }
+        Some((cursor.parent.clone(), cursor.next.clone()))
+    })
+}
+
+#[inline]
+fn advance (node: &Node) {
+    set_next(node.next_sibling())
+}
+
+#[inline]
+fn set_next (next: Option<Node>) {
+    CURSORS.with(|x| {
+        if let Some(cursor) = unsafe { &mut *x.get() }.last_mut() {
+            cursor.next = next;
+        }
+    })
+}
+
+/// Skips the whitespace between elements, which isn't rendered on the client.
+#[inline]
+fn skip_whitespace (cursor: &mut Cursor) {
+    while let Some(node) = cursor.next.take() {
+        if is_whitespace(&node) {
+            cursor.next = node.next_sibling();
+            continue;
+        }
+        cursor.next = Some(node);
+        break;
+    }
+}
+
+#[inline]
+fn is_whitespace (node: &Node) -> bool {
+    node.node_type() == TEXT_NODE && node.clone().into_text().data().trim().is_empty()
+}
+
+/// Puts `node` in place of the mismatched node `old` (or at the end of `parent`), moving the cursor past it.
+#[inline]
+fn replace (parent: &Node, node: &Node, old: Option<&Node>) {
+    let result = match old {
+        Some(old) => parent.replace_child(node, old),
+        None => parent.append_child(node),
+    };
+    if let Err(e) = result {
+        crate::eprintln!(&e)
+    }
+    advance(node);
+}
+
+#[inline]
+fn insert (parent: &Node, node: &Node, reference: Option<&Node>) {
+    if let Err(e) = parent.insert_before(node, reference) {
+        crate::eprintln!(&e)
+    }
+}
+
+#[inline]
+fn mismatch (expected: &str, found: Option<&Node>) {
+    #[cfg(debug_assertions)]
+    crate::eprintln!(
+        "hydration mismatch: expected {expected}, found {}",
+        found.map_or_else(|| String::from("nothing"), Node::node_name)
+    );
+    #[cfg(not(debug_assertions))]
+    let _ = (expected, found);
+}
@@ -0,0 +1,134 @@
+use super::{element::{Child, Element, Inner}, renderer::Renderer};
+use std::mem::take;
+
+/// Lifecycle hooks of an element
+#[derive(Default)]
+pub(super) struct Hooks {
+    mount: Vec<Box<dyn FnMut()>>,
+    unmount: Vec<Box<dyn FnMut()>>,
+    cleanup: Vec<Box<dyn FnOnce()>>,
+}
+
+impl<R: Renderer> Element<R> {
+    /// Returns `true` if the element is currently attached to the document.
+    #[inline]
+    pub fn is_mounted (&self) -> bool {
+        unsafe { &*self.inner.get() }.mounted
+    }
+
+    /// Invokes `f` every time the element is attached to the document.
+    ///
+    /// If the element is already attached, `f` is also invoked immediately.
+    #[inline]
+    pub fn on_mount (&self, f: impl 'static + FnMut()) {
+        self.on_mount_boxed(Box::new(f))
+    }
+
+    /// Invokes `f` every time the element is detached from the document, either because it (or one of its
+    /// ancestors) was removed or dropped.
+    #[inline]
+    pub fn on_unmount (&self, f: impl 'static + FnMut()) {
+        self.on_unmount_boxed(Box::new(f))
+    }
+
+    /// Invokes `f` once, when the element is dropped.
+    #[inline]
+    pub fn on_cleanup (&self, f: impl 'static + FnOnce()) {
+        self.on_cleanup_boxed(Box::new(f))
+    }
+
+    pub fn on_mount_boxed (&self, mut f: Box<dyn FnMut()>) {
+        let inner = unsafe { &mut *self.inner.get() };
+        if inner.mounted {
+            f()
+        }
+        inner.hooks.mount.push(f);
+    }
+
+    #[inline]
+    pub fn on_unmount_boxed (&self, f: Box<dyn FnMut()>) {
+        unsafe { &mut *self.inner.get() }.hooks.unmount.push(f);
+    }
+
+    #[inline]
+    pub fn on_cleanup_boxed (&self, f: Box<dyn FnOnce()>) {
+        unsafe { &mut *self.inner.get() }.hooks.cleanup.push(f);
+    }
+}
+
+impl<R: Renderer> Inner<R> {
+    /// Mounts the child at `idx` if this element is mounted.
+    #[inline]
+    pub(super) fn attach (&self, idx: usize) {
+        if self.mounted {
+            self.children[idx].mount()
+        }
+    }
+
+    /// Marks the element and its descendants as attached to the document, invoking their mount hooks.
+    pub(super) fn mount (&mut self) {
+        if self.mounted {
+            return;
+        }
+        self.mounted = true;
+
+        for (_, child) in self.children.iter() {
+            child.mount();
+        }
+
+        // Hooks may register new hooks, which are invoked as they're registered
+        let mut hooks = take(&mut self.hooks.mount);
+        hooks.iter_mut().for_each(|f| f());
+        hooks.append(&mut self.hooks.mount);
+        self.hooks.mount = hooks;
+    }
+
+    /// Marks the element and its descendants as detached from the document, invoking their unmount hooks.
+    pub(super) fn unmount (&mut self) {
+        if !self.mounted {
+            return;
+        }
+        self.mounted = false;
+
+        let mut hooks = take(&mut self.hooks.unmount);
+        hooks.iter_mut().for_each(|f| f());
+        hooks.append(&mut self.hooks.unmount);
+        self.hooks.unmount = hooks;
+
+        for (_, child) in self.children.iter() {
+            child.unmount();
+        }
+    }
+
+    /// Invokes the cleanup hooks of the element.
+    #[inline]
+    pub(super) fn cleanup (&mut self) {
+        take(&mut self.hooks.cleanup).into_iter().for_each(|f| f());
+    }
+}
+
+impl<R: Renderer> Child<R> {
+    /// Mounts the child, placing the content of portals into their target.
+    #[inline]
+    pub(super) fn mount (&self) {
+        if let Self::Portal(portal) = self {
+            portal.attach();
+        }
+        self.inner().mount()
+    }
+
+    /// Unmounts the child, removing the content of portals from their target.
+    #[inline]
+    pub(super) fn unmount (&self) {
+        self.inner().unmount();
+        if let Self::Portal(portal) = self {
+            portal.detach();
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[inline]
+    pub(super) fn inner (&self) -> &mut Inner<R> {
+        unsafe { &mut *self.element().inner.get() }
+    }
+}
@@ -0,0 +1,127 @@
+use super::{context::{current_scope, with_scope}, element::{Child, Element, Inner}, renderer::Renderer};
+use crate::state::Readable;
+use std::{collections::HashMap, hash::Hash, rc::Rc};
+use wasm_bindgen::JsValue;
+
+/// Children rendered by [`bind_list`](Element::bind_list)
+struct List<K, R: Renderer> {
+    /// Keys of the rendered items, alongside the index of their child in the parent
+    items: Vec<(K, usize)>,
+    /// Marks the end of the list inside of the parent
+    anchor: R::Node,
+}
+
+impl<R: Renderer> Element<R> {
+    /// Renders every item of `state` as a child of this element, keeping them in sync with the collection.
+    ///
+    /// Items are identified by the key returned by `key`. When the collection changes, only new items are
+    /// rendered and only removed ones are dropped, while the children of the remaining items are moved into
+    /// place, preserving their state.
+    pub fn bind_list<T, K, C, KF, RF>(
+        &self,
+        state: &Readable<Vec<T>>,
+        mut key: KF,
+        mut render: RF,
+    ) -> Result<(), JsValue>
+    where
+        T: 'static,
+        K: 'static + Eq + Hash,
+        C: Into<Child<R>>,
+        KF: 'static + FnMut(&T) -> K,
+        RF: 'static + FnMut(&T) -> C,
+    {
+        let inner = unsafe { &mut *self.inner.get() };
+
+        // The anchor follows the items, so it's adopted after them while hydrating
+        let mut items = Vec::new();
+        state.with(|x| reconcile(&mut items, inner, x, &mut key, &mut render))?;
+
+        let anchor = R::create_anchor();
+        R::append_child(inner.element.as_ref(), &anchor)?;
+
+        let mut list = List { items, anchor };
+        list.place(inner)?;
+
+        let parent = Rc::downgrade(&self.inner);
+        let scope = current_scope();
+        let sub = state.subscribe(move |items| {
+            if let Some(parent) = parent.upgrade() {
+                let parent = unsafe { &mut *parent.get() };
+                let result = with_scope(scope.clone(), || list.update(parent, items, &mut key, &mut render));
+                if let Err(e) = result {
+                    crate::eprintln!(&e)
+                }
+            }
+        });
+
+        inner.content.push(sub);
+        return Ok(());
+    }
+}
+
+impl<K: Eq + Hash, R: Renderer> List<K, R> {
+    #[inline]
+    fn update<T, C: Into<Child<R>>>(
+        &mut self,
+        parent: &mut Inner<R>,
+        items: &[T],
+        key: &mut impl FnMut(&T) -> K,
+        render: &mut impl FnMut(&T) -> C,
+    ) -> Result<(), JsValue> {
+        reconcile(&mut self.items, parent, items, key, render)?;
+        return self.place(parent);
+    }
+
+    /// Moves the children into place, starting from the end
+    fn place(&self, parent: &mut Inner<R>) -> Result<(), JsValue> {
+        let mut next = self.anchor.clone();
+        for (_, idx) in self.items.iter().rev() {
+            let node = parent.children[*idx].node();
+            if R::next_sibling(node).as_ref() != Some(&next) {
+                R::insert_before(parent.element.as_ref(), node, Some(&next))?;
+            }
+            next = node.clone();
+        }
+
+        return Ok(());
+    }
+}
+
+/// Renders the new items and removes the stale ones, without moving them into place
+fn reconcile<T, K: Eq + Hash, C: Into<Child<R>>, R: Renderer>(
+    current: &mut Vec<(K, usize)>,
+    parent: &mut Inner<R>,
+    items: &[T],
+    key: &mut impl FnMut(&T) -> K,
+    render: &mut impl FnMut(&T) -> C,
+) -> Result<(), JsValue> {
+    let mut prev = HashMap::with_capacity(current.len());
+    for (key, idx) in current.drain(..) {
+        if let Some(idx) = prev.insert(key, idx) {
+            remove_child(parent, idx)?;
+        }
+    }
+
+    for item in items {
+        let key = key(item);
+        let idx = match prev.remove(&key) {
+            Some(idx) => idx,
+            None => parent.children.insert(render(item).into()),
+        };
+        parent.attach(idx);
+        current.push((key, idx));
+    }
+
+    for (_, idx) in prev {
+        remove_child(parent, idx)?;
+    }
+
+    return Ok(());
+}
+
+#[inline]
+fn remove_child<R: Renderer>(parent: &mut Inner<R>, idx: usize) -> Result<(), JsValue> {
+    let child = parent.children.remove(idx);
+    R::remove_child(parent.element.as_ref(), child.node())?;
+    return Ok(());
+}
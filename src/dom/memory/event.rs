@@ -0,0 +1,406 @@
+use super::Node;
+use crate::dom::{attribute::{Property, PropertyValue}, event::FromEvent};
+use std::{cell::{Cell, RefCell}, ops::Deref, rc::Rc};
+
+/// A DOM event
+///
+/// Outside of WebAssembly, events are plain Rust values, and the fields of their specialised types (e.g.
+/// [`client_x`](MouseEvent::client_x)) are read from the values set with [`with_field`](Event::with_field),
+/// under their JavaScript name (e.g. `clientX`).
+#[derive(Debug, Clone)]
+pub struct Event {
+    inner: Rc<EventData>,
+}
+
+#[derive(Debug)]
+struct EventData {
+    event_type: String,
+    bubbles: bool,
+    cancelable: bool,
+    fields: RefCell<Vec<(String, Property)>>,
+    target: RefCell<Option<Node>>,
+    current_target: RefCell<Option<Node>>,
+    default_prevented: Cell<bool>,
+    propagation_stopped: Cell<bool>,
+    immediate_propagation_stopped: Cell<bool>,
+    /// Whether the listener being invoked is passive, and thus can't cancel the event
+    in_passive_listener: Cell<bool>,
+}
+
+impl Event {
+    /// Creates an event that doesn't bubble and can't be canceled.
+    #[inline]
+    pub fn new (event_type: &str) -> Self {
+        Self::with_options(event_type, false, false)
+    }
+
+    #[inline]
+    pub fn with_options (event_type: &str, bubbles: bool, cancelable: bool) -> Self {
+        return Self {
+            inner: Rc::new(EventData {
+                event_type: event_type.to_owned(),
+                bubbles,
+                cancelable,
+                fields: RefCell::new(Vec::new()),
+                target: RefCell::new(None),
+                current_target: RefCell::new(None),
+                default_prevented: Cell::new(false),
+                propagation_stopped: Cell::new(false),
+                immediate_propagation_stopped: Cell::new(false),
+                in_passive_listener: Cell::new(false),
+            }),
+        };
+    }
+
+    /// Sets the field `name` (e.g. `clientX`) of the event.
+    #[inline]
+    pub fn with_field (self, name: &str, value: impl PropertyValue) -> Self {
+        let value = value.to_property();
+        let mut fields = self.inner.fields.borrow_mut();
+        match fields.iter_mut().find(|(x, _)| x == name) {
+            Some((_, prev)) => *prev = value,
+            None => fields.push((name.to_owned(), value)),
+        }
+        drop(fields);
+        return self;
+    }
+
+    #[inline]
+    pub fn event_type (&self) -> String {
+        self.inner.event_type.clone()
+    }
+
+    #[inline]
+    pub fn target (&self) -> Option<Node> {
+        self.inner.target.borrow().clone()
+    }
+
+    #[inline]
+    pub fn current_target (&self) -> Option<Node> {
+        self.inner.current_target.borrow().clone()
+    }
+
+    #[inline]
+    pub fn bubbles (&self) -> bool {
+        self.inner.bubbles
+    }
+
+    #[inline]
+    pub fn cancelable (&self) -> bool {
+        self.inner.cancelable
+    }
+
+    #[inline]
+    pub fn default_prevented (&self) -> bool {
+        self.inner.default_prevented.get()
+    }
+
+    /// Always `false`, since events outside of WebAssembly are never dispatched by a user agent.
+    #[inline]
+    pub fn is_trusted (&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn time_stamp (&self) -> f64 {
+        self.field("timeStamp")
+    }
+
+    #[inline]
+    pub fn prevent_default (&self) {
+        if self.inner.cancelable && !self.inner.in_passive_listener.get() {
+            self.inner.default_prevented.set(true)
+        }
+    }
+
+    #[inline]
+    pub fn stop_propagation (&self) {
+        self.inner.propagation_stopped.set(true)
+    }
+
+    #[inline]
+    pub fn stop_immediate_propagation (&self) {
+        self.inner.propagation_stopped.set(true);
+        self.inner.immediate_propagation_stopped.set(true);
+    }
+
+    #[inline]
+    fn field<T: FromField> (&self, name: &str) -> T {
+        let fields = self.inner.fields.borrow();
+        T::from_field(fields.iter().find(|(x, _)| x == name).map(|(_, value)| value))
+    }
+}
+
+/* DISPATCH */
+impl Node {
+    /// Dispatches `event` to the node, as the browser would: capture listeners are invoked from the root down to
+    /// the node, followed by the node's own listeners and, if the event bubbles, by the listeners of its
+    /// ancestors back up to the root.
+    ///
+    /// Returns `false` if a listener canceled the event with [`prevent_default`](Event::prevent_default), and
+    /// `true` otherwise.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spiderweb::{client, dom::event::{Event, MouseEvent}, state::Writeable};
+    /// use std::rc::Rc;
+    ///
+    /// let clicks = Rc::new(Writeable::new(0));
+    /// let counter = clicks.clone();
+    /// let button = client! {
+    ///     <button on:click={move |_: MouseEvent| counter.update(|x| *x += 1)}>{"Click"}</button>
+    /// }.unwrap();
+    ///
+    /// button.html_node().dispatch_event(&Event::with_options("click", true, true));
+    /// assert_eq!(clicks.get(), 1);
+    /// ```
+    pub fn dispatch_event (&self, event: &Event) -> bool {
+        let data = &event.inner;
+        *data.target.borrow_mut() = Some(self.clone());
+
+        let mut ancestors = Vec::new();
+        let mut node = self.parent_node();
+        while let Some(current) = node {
+            node = current.parent_node();
+            ancestors.push(current);
+        }
+
+        let capturing = ancestors.iter().rev().map(|x| (x, true));
+        let at_target = [(self, true), (self, false)];
+        let bubbling = ancestors.iter().filter(|_| data.bubbles).map(|x| (x, false));
+
+        for (node, capture) in capturing.chain(at_target).chain(bubbling) {
+            if data.propagation_stopped.get() {
+                break;
+            }
+            node.invoke_listeners(event, capture);
+        }
+
+        *data.current_target.borrow_mut() = None;
+        data.propagation_stopped.set(false);
+        data.immediate_propagation_stopped.set(false);
+        return !data.default_prevented.get();
+    }
+
+    /// Invokes the listeners of the node registered for `event` during the specified phase.
+    fn invoke_listeners (&self, event: &Event, capture: bool) {
+        let data = &event.inner;
+        *data.current_target.borrow_mut() = Some(self.clone());
+
+        let listeners = self.inner.listeners.borrow()
+            .iter()
+            .filter(|x| x.capture == capture && x.event == data.event_type)
+            .map(|x| (x.f.clone(), x.once, x.passive))
+            .collect::<Vec<_>>();
+
+        for (f, once, passive) in listeners {
+            // Listeners removed by the previous ones aren't invoked
+            let mut registered = self.inner.listeners.borrow_mut();
+            let Some(idx) = registered.iter().position(|x| Rc::ptr_eq(&x.f, &f)) else {
+                continue;
+            };
+            if once {
+                registered.remove(idx);
+            }
+            drop(registered);
+
+            // Listeners that dispatch events to themselves aren't invoked again
+            if let Ok(mut f) = f.try_borrow_mut() {
+                data.in_passive_listener.set(passive);
+                f(event.clone());
+                data.in_passive_listener.set(false);
+            }
+
+            if data.immediate_propagation_stopped.get() {
+                break;
+            }
+        }
+    }
+}
+
+impl PartialEq for Event {
+    #[inline]
+    fn eq (&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl FromEvent for Event {
+    #[inline]
+    fn from_event (event: Event) -> Self {
+        event
+    }
+}
+
+impl InputEvent {
+    /// Returns the `value` of the event's target (e.g. an `<input>` or a `<textarea>`), if it has any.
+    #[inline]
+    pub fn value (&self) -> Option<String> {
+        let target = self.target()?;
+        if target.node_type() != super::ELEMENT_NODE {
+            return None;
+        }
+
+        let target = target.into_element();
+        return match target.get_property("value") {
+            Some(Property::String(x)) => Some(x),
+            Some(Property::Number(x)) => Some(x.to_string()),
+            _ => target.get_attribute("value"),
+        };
+    }
+}
+
+/// A type that the value of an event's field can be read as, falling back to a default if it isn't set.
+trait FromField {
+    fn from_field (value: Option<&Property>) -> Self;
+}
+
+impl FromField for bool {
+    #[inline]
+    fn from_field (value: Option<&Property>) -> Self {
+        matches!(value, Some(Property::Bool(true)))
+    }
+}
+
+impl FromField for String {
+    #[inline]
+    fn from_field (value: Option<&Property>) -> Self {
+        Option::<String>::from_field(value).unwrap_or_default()
+    }
+}
+
+impl FromField for Option<String> {
+    #[inline]
+    fn from_field (value: Option<&Property>) -> Self {
+        match value {
+            Some(Property::String(x)) => Some(x.clone()),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_field {
+    ($($t:ty),+) => {
+        $(
+            impl FromField for $t {
+                #[inline]
+                fn from_field (value: Option<&Property>) -> Self {
+                    match value {
+                        Some(Property::Number(x)) => *x as $t,
+                        _ => 0 as $t,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_field! { i16, i32, u16, u32, f32, f64 }
+
+macro_rules! impl_event {
+    ($($(#[$meta:meta])* $name:ident: $parent:ident { $($field:ident: $t:ty = $js:literal),* $(,)? }),+ $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct $name($parent);
+
+            impl $name {
+                $(
+                    #[inline]
+                    pub fn $field (&self) -> $t {
+                        Event::field(self, $js)
+                    }
+                )*
+            }
+
+            impl Deref for $name {
+                type Target = $parent;
+
+                #[inline]
+                fn deref (&self) -> &$parent {
+                    &self.0
+                }
+            }
+
+            impl FromEvent for $name {
+                #[inline]
+                fn from_event (event: Event) -> Self {
+                    Self($parent::from_event(event))
+                }
+            }
+
+            impl From<$name> for Event {
+                #[inline]
+                fn from (value: $name) -> Self {
+                    Event::clone(&value)
+                }
+            }
+        )+
+    };
+}
+
+impl_event! {
+    /// An event caused by a user interaction
+    UiEvent: Event {
+        detail: i32 = "detail",
+    },
+    /// An event caused by the user interacting with a pointing device (e.g. a mouse)
+    MouseEvent: UiEvent {
+        client_x: f64 = "clientX",
+        client_y: f64 = "clientY",
+        page_x: f64 = "pageX",
+        page_y: f64 = "pageY",
+        screen_x: f64 = "screenX",
+        screen_y: f64 = "screenY",
+        offset_x: f64 = "offsetX",
+        offset_y: f64 = "offsetY",
+        movement_x: f64 = "movementX",
+        movement_y: f64 = "movementY",
+        button: i16 = "button",
+        buttons: u16 = "buttons",
+        alt_key: bool = "altKey",
+        ctrl_key: bool = "ctrlKey",
+        shift_key: bool = "shiftKey",
+        meta_key: bool = "metaKey",
+    },
+    /// An event caused by the user interacting with the keyboard
+    KeyboardEvent: UiEvent {
+        key: String = "key",
+        code: String = "code",
+        location: u32 = "location",
+        repeat: bool = "repeat",
+        is_composing: bool = "isComposing",
+        alt_key: bool = "altKey",
+        ctrl_key: bool = "ctrlKey",
+        shift_key: bool = "shiftKey",
+        meta_key: bool = "metaKey",
+    },
+    /// An event notifying of changes to editable content
+    InputEvent: UiEvent {
+        data: Option<String> = "data",
+        input_type: String = "inputType",
+        is_composing: bool = "isComposing",
+    },
+    /// An event related to focus (e.g. `focus` or `blur`)
+    FocusEvent: UiEvent {},
+    /// The event fired when a form is submitted
+    SubmitEvent: Event {},
+    /// An event caused by a pointer (e.g. a mouse, a pen or a touch contact)
+    PointerEvent: MouseEvent {
+        pointer_id: i32 = "pointerId",
+        pointer_type: String = "pointerType",
+        is_primary: bool = "isPrimary",
+        width: f64 = "width",
+        height: f64 = "height",
+        pressure: f32 = "pressure",
+    },
+    /// An event caused by the user rotating a mouse wheel (or similar device)
+    WheelEvent: MouseEvent {
+        delta_x: f64 = "deltaX",
+        delta_y: f64 = "deltaY",
+        delta_z: f64 = "deltaZ",
+        delta_mode: u32 = "deltaMode",
+    },
+    /// An event caused by a drag and drop interaction
+    DragEvent: MouseEvent {},
+}
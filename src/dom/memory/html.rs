@@ -0,0 +1,108 @@
+use super::{HtmlElement, Node, NodeKind};
+use crate::dom::attribute::Property;
+
+/// Elements that can't have any content, and thus have no closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+/// Elements whose text isn't escaped
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Properties that are serialised as attributes, since they reflect the element's initial state
+const REFLECTED_PROPERTIES: &[&str] = &["value", "checked", "selected", "disabled"];
+
+impl HtmlElement {
+    /// Serialises the element and its descendants into HTML.
+    #[inline]
+    pub fn outer_html (&self) -> String {
+        let mut result = String::new();
+        write_node(&mut result, self, false);
+        return result;
+    }
+
+    /// Serialises the descendants of the element into HTML.
+    #[inline]
+    pub fn inner_html (&self) -> String {
+        let mut result = String::new();
+        write_children(&mut result, self, RAW_TEXT_ELEMENTS.contains(&self.tag()));
+        return result;
+    }
+}
+
+fn write_node (out: &mut String, node: &Node, raw: bool) {
+    match node.inner.kind {
+        NodeKind::Document => write_children(out, node, false),
+        NodeKind::Text(ref data) => match raw {
+            true => out.push_str(&data.borrow()),
+            false => escape(out, &data.borrow(), false),
+        },
+        // Comments are the anchors of reactive regions, which are adopted while hydrating
+        NodeKind::Comment(ref data) => {
+            out.push_str("<!--");
+            out.push_str(&data.borrow());
+            out.push_str("-->");
+        }
+        NodeKind::Element { ref tag, ref attributes, ref properties } => {
+            out.push('<');
+            out.push_str(tag);
+
+            let attributes = attributes.borrow();
+            for (name, value) in attributes.iter() {
+                write_attribute(out, name, value);
+            }
+
+            for (name, value) in properties.borrow().iter() {
+                if !REFLECTED_PROPERTIES.contains(&name.as_str()) || attributes.iter().any(|(x, _)| x == name) {
+                    continue;
+                }
+                match value {
+                    Property::Bool(true) => write_attribute(out, name, ""),
+                    Property::Number(x) => write_attribute(out, name, &x.to_string()),
+                    Property::String(x) => write_attribute(out, name, x),
+                    _ => {}
+                }
+            }
+
+            out.push('>');
+            if VOID_ELEMENTS.contains(&tag.as_str()) {
+                return;
+            }
+
+            write_children(out, node, RAW_TEXT_ELEMENTS.contains(&tag.as_str()));
+            out.push_str("</");
+            out.push_str(tag);
+            out.push('>');
+        }
+    }
+}
+
+#[inline]
+fn write_children (out: &mut String, node: &Node, raw: bool) {
+    for child in node.inner.children.borrow().iter() {
+        write_node(out, child, raw);
+    }
+}
+
+#[inline]
+fn write_attribute (out: &mut String, name: &str, value: &str) {
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    escape(out, value, true);
+    out.push('"');
+}
+
+#[inline]
+fn escape (out: &mut String, s: &str, attribute: bool) {
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if attribute => out.push_str("&quot;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            c => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! In-memory implementation of the document, used outside of WebAssembly (e.g. to render HTML on the server).
+
+use super::{attribute::Property, event::{Event, ListenerOptions}};
+use selector::Selector;
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    ops::Deref,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::JsValue;
+
+pub(super) mod event;
+mod html;
+mod selector;
+
+/// Renderer of the in-memory document
+#[derive(Debug, Clone, Copy)]
+pub struct Memory;
+
+/// Callback of an event listener, kept alive for as long as the listener is registered
+pub(super) type Callback = Rc<RefCell<Box<dyn FnMut(Event)>>>;
+
+const ELEMENT_NODE: u16 = 1;
+const TEXT_NODE: u16 = 3;
+const COMMENT_NODE: u16 = 8;
+const DOCUMENT_NODE: u16 = 9;
+
+/// A node of the document
+#[derive(Clone)]
+pub struct Node {
+    inner: Rc<NodeData>,
+}
+
+/// An HTML element of the document
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlElement(Node);
+
+/// A text node of the document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text(Node);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Comment(Node);
+
+/// Nodes receive events directly outside of WebAssembly.
+pub type EventTarget = Node;
+
+/// The document every element belongs to
+pub struct Document {
+    root: Node,
+    body: HtmlElement,
+}
+
+struct NodeData {
+    kind: NodeKind,
+    parent: RefCell<Weak<NodeData>>,
+    children: RefCell<Vec<Node>>,
+    listeners: RefCell<Vec<Listener>>,
+}
+
+enum NodeKind {
+    Document,
+    Element {
+        tag: String,
+        attributes: RefCell<Vec<(String, String)>>,
+        properties: RefCell<Vec<(String, Property)>>,
+    },
+    Text(RefCell<String>),
+    Comment(RefCell<String>),
+}
+
+struct Listener {
+    event: String,
+    capture: bool,
+    once: bool,
+    passive: bool,
+    f: Callback,
+}
+
+impl Node {
+    #[inline]
+    fn new (kind: NodeKind) -> Self {
+        return Self {
+            inner: Rc::new(NodeData {
+                kind,
+                parent: RefCell::new(Weak::new()),
+                children: RefCell::new(Vec::new()),
+                listeners: RefCell::new(Vec::new()),
+            }),
+        };
+    }
+
+    pub(super) fn append_child (&self, child: &Node) -> Result<Node, JsValue> {
+        child.detach();
+        *child.inner.parent.borrow_mut() = Rc::downgrade(&self.inner);
+        self.inner.children.borrow_mut().push(child.clone());
+        return Ok(child.clone());
+    }
+
+    pub(super) fn remove_child (&self, child: &Node) -> Result<Node, JsValue> {
+        if child.parent_node().as_ref() != Some(self) {
+            return Err(crate::error_value("the node to be removed is not a child of this node"));
+        }
+        child.detach();
+        return Ok(child.clone());
+    }
+
+    pub(super) fn insert_before (&self, child: &Node, reference: Option<&Node>) -> Result<Node, JsValue> {
+        let Some(reference) = reference else {
+            return self.append_child(child);
+        };
+        if reference == child {
+            return Ok(child.clone());
+        }
+        if reference.parent_node().as_ref() != Some(self) {
+            return Err(crate::error_value("the reference node is not a child of this node"));
+        }
+
+        child.detach();
+        *child.inner.parent.borrow_mut() = Rc::downgrade(&self.inner);
+        let mut children = self.inner.children.borrow_mut();
+        let idx = children.iter().position(|x| x == reference).unwrap_or(children.len());
+        children.insert(idx, child.clone());
+        return Ok(child.clone());
+    }
+
+    pub(super) fn replace_child (&self, child: &Node, old: &Node) -> Result<Node, JsValue> {
+        if old.parent_node().as_ref() != Some(self) {
+            return Err(crate::error_value("the node to be replaced is not a child of this node"));
+        }
+        if child == old {
+            return Ok(old.clone());
+        }
+
+        self.insert_before(child, Some(old))?;
+        old.detach();
+        return Ok(old.clone());
+    }
+
+    /// Replaces the children of the node with a single text node (or none, if `s` is empty).
+    pub(super) fn set_text_content (&self, s: &str) {
+        match self.inner.kind {
+            NodeKind::Text(ref data) | NodeKind::Comment(ref data) => *data.borrow_mut() = s.to_owned(),
+            _ => {
+                for child in self.inner.children.take() {
+                    *child.inner.parent.borrow_mut() = Weak::new();
+                }
+                if !s.is_empty() {
+                    let _ = self.append_child(&Text::new(s));
+                }
+            }
+        }
+    }
+
+    /// Returns the text of the node and its descendants.
+    pub fn text_content (&self) -> String {
+        match self.inner.kind {
+            NodeKind::Text(ref data) | NodeKind::Comment(ref data) => data.borrow().clone(),
+            _ => {
+                let mut result = String::new();
+                for child in self.inner.children.borrow().iter() {
+                    if child.node_type() != COMMENT_NODE {
+                        result.push_str(&child.text_content());
+                    }
+                }
+                result
+            }
+        }
+    }
+
+    #[inline]
+    pub fn parent_node (&self) -> Option<Node> {
+        self.inner.parent.borrow().upgrade().map(|inner| Node { inner })
+    }
+
+    #[inline]
+    pub fn node_type (&self) -> u16 {
+        match self.inner.kind {
+            NodeKind::Document => DOCUMENT_NODE,
+            NodeKind::Element { .. } => ELEMENT_NODE,
+            NodeKind::Text(_) => TEXT_NODE,
+            NodeKind::Comment(_) => COMMENT_NODE,
+        }
+    }
+
+    /// Returns the name of the node, which is the uppercase tag of elements.
+    #[inline]
+    pub fn node_name (&self) -> String {
+        match self.inner.kind {
+            NodeKind::Document => String::from("#document"),
+            NodeKind::Element { ref tag, .. } => tag.to_ascii_uppercase(),
+            NodeKind::Text(_) => String::from("#text"),
+            NodeKind::Comment(_) => String::from("#comment"),
+        }
+    }
+
+    #[inline]
+    pub fn first_child (&self) -> Option<Node> {
+        self.inner.children.borrow().first().cloned()
+    }
+
+    #[inline]
+    pub fn next_sibling (&self) -> Option<Node> {
+        let parent = self.parent_node()?;
+        let children = parent.inner.children.borrow();
+        let idx = children.iter().position(|x| x == self)?;
+        return children.get(idx + 1).cloned();
+    }
+
+    /// Returns the children of the node.
+    #[inline]
+    pub fn child_nodes (&self) -> Vec<Node> {
+        self.inner.children.borrow().clone()
+    }
+
+    /// Returns `true` if the node is inside of a document.
+    #[inline]
+    pub fn is_connected (&self) -> bool {
+        let mut node = self.clone();
+        loop {
+            if node.node_type() == DOCUMENT_NODE {
+                return true;
+            }
+            match node.parent_node() {
+                Some(parent) => node = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Returns the first element under the node (in document order) matching the CSS `selector`.
+    ///
+    /// Selectors are made of tags, ids, classes and attributes, combined with descendant and child combinators
+    /// (e.g. `ul > li.done`, `input[type=checkbox]` or `#app p, footer`).
+    #[inline]
+    pub fn query_selector (&self, selector: &str) -> Result<Option<HtmlElement>, JsValue> {
+        let selector = parse_selector(selector)?;
+        let mut result = None;
+        self.for_each_element(&mut |element| {
+            let found = selector.matches(element);
+            if found {
+                result = Some(element.clone());
+            }
+            found
+        });
+        return Ok(result);
+    }
+
+    /// Returns every element under the node (in document order) matching the CSS `selector`.
+    #[inline]
+    pub fn query_selector_all (&self, selector: &str) -> Result<Vec<HtmlElement>, JsValue> {
+        let selector = parse_selector(selector)?;
+        let mut result = Vec::new();
+        self.for_each_element(&mut |element| {
+            if selector.matches(element) {
+                result.push(element.clone());
+            }
+            false
+        });
+        return Ok(result);
+    }
+
+    /// Invokes `f` for every element under the node (in document order), until it returns `true`.
+    fn for_each_element (&self, f: &mut impl FnMut(&HtmlElement) -> bool) -> bool {
+        for child in self.inner.children.borrow().iter() {
+            if child.node_type() == ELEMENT_NODE && f(&child.clone().into_element()) {
+                return true;
+            }
+            if child.for_each_element(f) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /// Casts the node into an element, without checking its type.
+    #[inline]
+    pub(super) fn into_element (self) -> HtmlElement {
+        HtmlElement(self)
+    }
+
+    /// Casts the node into a text node, without checking its type.
+    #[inline]
+    pub(super) fn into_text (self) -> Text {
+        Text(self)
+    }
+
+    /// Casts the node into a comment, without checking its type.
+    #[inline]
+    pub(super) fn into_comment (self) -> Comment {
+        Comment(self)
+    }
+
+    /// Registers `f` as a listener of `event`, returning the callback needed to remove it.
+    ///
+    /// Abort signals are JavaScript objects, so the listener's [`signal`](ListenerOptions::signal) is ignored.
+    pub(super) fn listen (&self, event: &str, f: Box<dyn FnMut(Event)>, options: &ListenerOptions) -> Callback {
+        let f = Rc::new(RefCell::new(f));
+        self.inner.listeners.borrow_mut().push(Listener {
+            event: event.to_owned(),
+            capture: options.capture,
+            once: options.once,
+            passive: options.passive,
+            f: f.clone(),
+        });
+        return f;
+    }
+
+    #[inline]
+    pub(super) fn unlisten (&self, event: &str, f: &Callback, capture: bool) {
+        self.inner.listeners.borrow_mut().retain(|x| {
+            x.event != event || x.capture != capture || !Rc::ptr_eq(&x.f, f)
+        });
+    }
+
+    /// Removes the node from its parent.
+    fn detach (&self) {
+        let parent = self.inner.parent.replace(Weak::new());
+        if let Some(parent) = parent.upgrade() {
+            parent.children.borrow_mut().retain(|x| x != self);
+        }
+    }
+}
+
+impl HtmlElement {
+    /// Returns the lowercase tag of the element.
+    #[inline]
+    pub fn tag (&self) -> &str {
+        match self.inner.kind {
+            NodeKind::Element { ref tag, .. } => tag,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the value of the attribute `name`, if it's present.
+    #[inline]
+    pub fn get_attribute (&self, name: &str) -> Option<String> {
+        self.attributes().borrow().iter().find(|(x, _)| x == name).map(|(_, value)| value.clone())
+    }
+
+    pub(super) fn set_attribute (&self, name: &str, value: &str) -> Result<(), JsValue> {
+        let mut attributes = self.attributes().borrow_mut();
+        match attributes.iter_mut().find(|(x, _)| x == name) {
+            Some((_, prev)) => *prev = value.to_owned(),
+            None => attributes.push((name.to_owned(), value.to_owned())),
+        }
+        return Ok(());
+    }
+
+    #[inline]
+    pub(super) fn remove_attribute (&self, name: &str) -> Result<(), JsValue> {
+        self.attributes().borrow_mut().retain(|(x, _)| x != name);
+        return Ok(());
+    }
+
+    /// Adds the class `name` if `force` is `true`, and removes it otherwise.
+    pub(super) fn toggle_class (&self, name: &str, force: bool) -> Result<(), JsValue> {
+        let class = self.get_attribute("class").unwrap_or_default();
+        let mut classes = class.split_ascii_whitespace().filter(|x| *x != name).collect::<Vec<_>>();
+        if force {
+            classes.push(name);
+        }
+        return self.set_attribute("class", &classes.join(" "));
+    }
+
+    /// Sets the CSS property `prop` of the element's inline style, removing it if `value` is empty.
+    pub(super) fn set_style (&self, prop: &str, value: &str) -> Result<(), JsValue> {
+        let style = self.get_attribute("style").unwrap_or_default();
+        let mut declarations = style
+            .split(';')
+            .filter_map(|x| x.split_once(':'))
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .filter(|(name, _)| *name != prop)
+            .collect::<Vec<_>>();
+
+        if !value.is_empty() {
+            declarations.push((prop, value));
+        }
+
+        let style = declarations.into_iter().map(|(name, value)| format!("{name}: {value};")).collect::<Vec<_>>();
+        return self.set_attribute("style", &style.join(" "));
+    }
+
+    /// Returns the value of the property `name`, if it has been set.
+    #[inline]
+    pub fn get_property (&self, name: &str) -> Option<Property> {
+        self.properties().borrow().iter().find(|(x, _)| x == name).map(|(_, value)| value.clone())
+    }
+
+    pub(super) fn set_property (&self, name: &str, value: &Property) -> Result<(), JsValue> {
+        let mut properties = self.properties().borrow_mut();
+        match properties.iter_mut().find(|(x, _)| x == name) {
+            Some((_, prev)) => *prev = value.clone(),
+            None => properties.push((name.to_owned(), value.clone())),
+        }
+        return Ok(());
+    }
+
+    /// Clicks the element, as if the user did.
+    ///
+    /// Clicking a checkbox toggles its `checked` property, and `input` and `change` events follow. If the `click`
+    /// event is canceled, the property is restored and no other event follows. Returns `false` if the event was
+    /// canceled.
+    pub fn click (&self) -> bool {
+        let checkable = self.tag() == "input" && self.get_attribute("type").is_some_and(|x| x.eq_ignore_ascii_case("checkbox"));
+        let checked = self.is_checked();
+        if checkable {
+            let _ = self.set_property("checked", &Property::Bool(!checked));
+        }
+
+        let event = Event::with_options("click", true, true).with_field("detail", 1);
+        let result = self.dispatch_event(&event);
+
+        match (checkable, result) {
+            (true, true) => {
+                self.dispatch_event(&Event::with_options("input", true, false));
+                self.dispatch_event(&Event::with_options("change", true, false));
+            }
+            (true, false) => {
+                let _ = self.set_property("checked", &Property::Bool(checked));
+            }
+            _ => {}
+        }
+        return result;
+    }
+
+    /// Replaces the `value` of the element (e.g. an `<input>`) and dispatches an `input` event, as if the user
+    /// typed it.
+    pub fn input (&self, value: &str) {
+        let _ = self.set_property("value", &Property::String(value.to_owned()));
+        let event = Event::with_options("input", true, false)
+            .with_field("data", value)
+            .with_field("inputType", "insertReplacementText");
+        self.dispatch_event(&event);
+    }
+
+    /// Dispatches a `change` event, which form controls fire once their value is committed (e.g. on blur).
+    #[inline]
+    pub fn change (&self) {
+        self.dispatch_event(&Event::with_options("change", true, false));
+    }
+
+    /// Dispatches a `submit` event, as if the element (a `<form>`) was submitted by the user.
+    ///
+    /// Returns `false` if the event was canceled.
+    #[inline]
+    pub fn submit (&self) -> bool {
+        self.dispatch_event(&Event::with_options("submit", true, true))
+    }
+
+    /// Returns `true` if the `checked` property (or, if it isn't set, the attribute) of the element is present.
+    #[inline]
+    fn is_checked (&self) -> bool {
+        match self.get_property("checked") {
+            Some(Property::Bool(x)) => x,
+            _ => self.get_attribute("checked").is_some(),
+        }
+    }
+
+    #[inline]
+    fn attributes (&self) -> &RefCell<Vec<(String, String)>> {
+        match self.inner.kind {
+            NodeKind::Element { ref attributes, .. } => attributes,
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn properties (&self) -> &RefCell<Vec<(String, Property)>> {
+        match self.inner.kind {
+            NodeKind::Element { ref properties, .. } => properties,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Text {
+    #[inline]
+    pub(super) fn new (s: &str) -> Self {
+        Self(Node::new(NodeKind::Text(RefCell::new(s.to_owned()))))
+    }
+
+    #[inline]
+    pub(super) fn data (&self) -> String {
+        self.text_content()
+    }
+
+    #[inline]
+    pub(super) fn set_data (&self, s: &str) {
+        self.set_text_content(s)
+    }
+
+    /// Splits the node at `offset` (in UTF-16 code units), inserting the remainder right after it.
+    pub(super) fn split_text (&self, offset: u32) -> Text {
+        let NodeKind::Text(ref data) = self.inner.kind else { unreachable!() };
+        let mut data = data.borrow_mut();
+
+        let idx = data
+            .char_indices()
+            .scan(0, |units, (idx, c)| {
+                let current = *units;
+                *units += c.len_utf16() as u32;
+                Some((idx, current))
+            })
+            .find(|(_, units)| *units >= offset)
+            .map_or(data.len(), |(idx, _)| idx);
+
+        let rest = Text::new(&data.split_off(idx));
+        if let Some(parent) = self.parent_node() {
+            let _ = parent.insert_before(&rest, self.next_sibling().as_ref());
+        }
+        return rest;
+    }
+}
+
+impl Comment {
+    #[inline]
+    pub(super) fn new (s: &str) -> Self {
+        Self(Node::new(NodeKind::Comment(RefCell::new(s.to_owned()))))
+    }
+}
+
+impl Document {
+    #[inline]
+    fn new () -> Self {
+        let root = Node::new(NodeKind::Document);
+        let body = Self::element("body");
+        let _ = root.append_child(&body);
+        return Self { root, body };
+    }
+
+    #[inline]
+    pub(super) fn body (&self) -> HtmlElement {
+        self.body.clone()
+    }
+
+    #[inline]
+    pub(super) fn create_element (&self, tag: &str) -> HtmlElement {
+        Self::element(tag)
+    }
+
+    /// Returns the first element of the document matching `selector`.
+    #[inline]
+    pub(super) fn query_selector (&self, selector: &str) -> Result<Option<HtmlElement>, JsValue> {
+        self.root.query_selector(selector)
+    }
+
+    #[inline]
+    fn element (tag: &str) -> HtmlElement {
+        HtmlElement(Node::new(NodeKind::Element {
+            tag: tag.to_ascii_lowercase(),
+            attributes: RefCell::new(Vec::new()),
+            properties: RefCell::new(Vec::new()),
+        }))
+    }
+}
+
+#[inline]
+pub(super) fn document () -> Document {
+    Document::new()
+}
+
+#[inline]
+fn parse_selector (selector: &str) -> Result<Selector, JsValue> {
+    Selector::parse(selector).ok_or_else(|| crate::error_value(&format!("'{selector}' is not a valid selector")))
+}
+
+impl PartialEq for Node {
+    #[inline]
+    fn eq (&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Debug for Node {
+    #[inline]
+    fn fmt (&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Node").field(&self.node_name()).finish()
+    }
+}
+
+macro_rules! impl_node {
+    ($($t:ident),+) => {
+        $(
+            impl Deref for $t {
+                type Target = Node;
+
+                #[inline]
+                fn deref (&self) -> &Node {
+                    &self.0
+                }
+            }
+
+            impl From<$t> for Node {
+                #[inline]
+                fn from (value: $t) -> Self {
+                    value.0
+                }
+            }
+
+            impl AsRef<Node> for $t {
+                #[inline]
+                fn as_ref (&self) -> &Node {
+                    &self.0
+                }
+            }
+        )+
+    };
+}
+
+impl_node! { HtmlElement, Text, Comment }
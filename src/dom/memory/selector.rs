@@ -0,0 +1,198 @@
+use super::{HtmlElement, Node, ELEMENT_NODE};
+
+/// A list of complex selectors (e.g. `ul > li.done, #app p`), matching elements that match any of them.
+pub(super) struct Selector {
+    groups: Vec<Vec<(Combinator, Compound)>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Combinator {
+    /// The first compound of a group
+    None,
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+}
+
+#[derive(Default)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    /// Parses `selector`, made of tags, ids, classes and attributes (e.g. `input[type=checkbox]`), combined with
+    /// descendant and child combinators.
+    ///
+    /// Returns `None` if the selector is invalid, or uses unsupported features (e.g. pseudo-classes).
+    pub(super) fn parse (selector: &str) -> Option<Self> {
+        let mut groups = Vec::new();
+        for group in selector.split(',') {
+            let mut compounds = Vec::new();
+            // Combinator between the last compound and the next one
+            let mut pending = None;
+
+            for token in tokenize(group)? {
+                match token {
+                    " " if compounds.is_empty() => {}
+                    " " => pending = pending.or(Some(Combinator::Descendant)),
+                    ">" if compounds.is_empty() || pending == Some(Combinator::Child) => return None,
+                    ">" => pending = Some(Combinator::Child),
+                    token => {
+                        let combinator = match compounds.is_empty() {
+                            true => Combinator::None,
+                            false => pending?,
+                        };
+                        compounds.push((combinator, Compound::parse(token)?));
+                        pending = None;
+                    }
+                }
+            }
+
+            if compounds.is_empty() || pending == Some(Combinator::Child) {
+                return None;
+            }
+            groups.push(compounds);
+        }
+
+        return Some(Self { groups });
+    }
+
+    pub(super) fn matches (&self, element: &HtmlElement) -> bool {
+        self.groups.iter().any(|group| matches_group(group, element))
+    }
+}
+
+impl Compound {
+    fn parse (s: &str) -> Option<Self> {
+        let mut result = Self::default();
+        let mut rest = s;
+
+        let tag_len = rest.find(['#', '.', '[']).unwrap_or(rest.len());
+        let (tag, tail) = rest.split_at(tag_len);
+        if !tag.is_empty() && tag != "*" {
+            result.tag = Some(tag.to_ascii_lowercase());
+        }
+        rest = tail;
+
+        while let Some(prefix) = rest.chars().next() {
+            let tail = &rest[1..];
+            match prefix {
+                '[' => {
+                    let end = tail.find(']')?;
+                    let attribute = match tail[..end].split_once('=') {
+                        Some((name, value)) => (name.trim().to_owned(), Some(value.trim().trim_matches(['"', '\'']).to_owned())),
+                        None => (tail[..end].trim().to_owned(), None),
+                    };
+                    result.attributes.push(attribute);
+                    rest = &tail[end + 1..];
+                }
+                '#' | '.' => {
+                    let len = tail.find(['#', '.', '[']).unwrap_or(tail.len());
+                    let name = &tail[..len];
+                    if name.is_empty() {
+                        return None;
+                    }
+                    match prefix {
+                        '#' => result.id = Some(name.to_owned()),
+                        _ => result.classes.push(name.to_owned()),
+                    }
+                    rest = &tail[len..];
+                }
+                _ => return None,
+            }
+        }
+
+        if s.is_empty() || result.tag.as_deref().is_some_and(|x| !x.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')) {
+            return None;
+        }
+        return Some(result);
+    }
+
+    fn matches (&self, element: &HtmlElement) -> bool {
+        if self.tag.as_deref().is_some_and(|x| x != element.tag()) {
+            return false;
+        }
+        if self.id.is_some() && element.get_attribute("id") != self.id {
+            return false;
+        }
+
+        let classes = element.get_attribute("class").unwrap_or_default();
+        if !self.classes.iter().all(|x| classes.split_ascii_whitespace().any(|y| x == y)) {
+            return false;
+        }
+
+        return self.attributes.iter().all(|(name, value)| match (element.get_attribute(name), value) {
+            (Some(actual), Some(expected)) => actual == *expected,
+            (found, None) => found.is_some(),
+            (None, Some(_)) => false,
+        });
+    }
+}
+
+/// Splits a complex selector into compounds, `" "` and `">"`.
+fn tokenize (s: &str) -> Option<Vec<&str>> {
+    let mut result = Vec::new();
+    let mut start = None;
+    let mut brackets = false;
+
+    for (idx, c) in s.char_indices() {
+        match c {
+            '[' => brackets = true,
+            ']' => brackets = false,
+            _ if brackets => {}
+            '>' | ' ' | '\t' | '\n' => {
+                if let Some(start) = start.take() {
+                    result.push(&s[start..idx]);
+                }
+                result.push(if c == '>' { ">" } else { " " });
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(idx);
+    }
+
+    if brackets {
+        return None;
+    }
+    if let Some(start) = start {
+        result.push(&s[start..]);
+    }
+    return Some(result);
+}
+
+/// Matches the compounds of a group from right to left, walking up the ancestors of `element`.
+fn matches_group (group: &[(Combinator, Compound)], element: &HtmlElement) -> bool {
+    let Some(((combinator, last), rest)) = group.split_last() else {
+        return true;
+    };
+    if !last.matches(element) {
+        return false;
+    }
+
+    let mut parent = parent_element(element);
+    match combinator {
+        Combinator::None => true,
+        Combinator::Child => parent.is_some_and(|x| matches_group(rest, &x)),
+        Combinator::Descendant => {
+            while let Some(ancestor) = parent {
+                if matches_group(rest, &ancestor) {
+                    return true;
+                }
+                parent = parent_element(&ancestor);
+            }
+            false
+        }
+    }
+}
+
+#[inline]
+fn parent_element (node: &Node) -> Option<HtmlElement> {
+    node.parent_node()
+        .filter(|x| x.node_type() == ELEMENT_NODE)
+        .map(Node::into_element)
+}
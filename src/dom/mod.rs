@@ -1,2 +1,64 @@
+use wasm_bindgen::JsValue;
+
 pub mod element;
-pub mod component;
\ No newline at end of file
+pub mod component;
+pub mod attribute;
+mod binding;
+pub mod event;
+mod list;
+mod condition;
+mod lifecycle;
+mod children;
+mod context;
+mod portal;
+mod mount;
+mod hydration;
+mod renderer;
+#[cfg(target_family = "wasm")]
+mod web;
+#[cfg(not(target_family = "wasm"))]
+mod memory;
+#[cfg(not(target_family = "wasm"))]
+pub mod testing;
+
+#[cfg(target_family = "wasm")]
+use web as backend;
+#[cfg(not(target_family = "wasm"))]
+use memory as backend;
+
+pub use element::Element;
+pub use renderer::{DefaultRenderer, Renderer};
+#[cfg(target_family = "wasm")]
+pub use web::Dom;
+#[cfg(not(target_family = "wasm"))]
+pub use memory::Memory;
+pub use condition::{Show, Switch};
+pub use children::Children;
+pub use portal::Portal;
+pub use mount::{hydrate, mount_to, Mount, MountTarget};
+pub use context::{provide_context, use_context, render};
+#[doc(hidden)]
+pub use condition::render_branch;
+#[doc(hidden)]
+pub use children::IntoRender;
+
+/// A type that can be rendered into an [`Element`].
+///
+/// Capitalised tags inside [`client!`](crate::client) are rendered through [`render`], which builds the
+/// component's [`Props`](Component::Props) from the tag's attributes and passes the tag's content as [`Children`].
+/// Missing props are filled with their defaults if the tag ends with `..` (e.g. `<Counter start={1} ../>`), as
+/// long as they implement [`Default`].
+///
+/// Components are usually declared with the [`component`](crate::component) attribute, and can support
+/// several renderers by implementing this trait for each of them (or for any [`Renderer`]).
+pub trait Component<R: Renderer = DefaultRenderer> {
+    type Props;
+    fn render (props: Self::Props, children: Children<'_, R>) -> Result<Element<R>, JsValue>;
+}
+
+/// The [`Props`](Component::Props) of a component.
+pub type PropsOf<C, R = DefaultRenderer> = <C as Component<R>>::Props;
+
+/// Props of components that don't take any.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoProps;
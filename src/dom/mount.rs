@@ -0,0 +1,152 @@
+use super::{element::{Child, Element, HtmlElement}, renderer::{DefaultRenderer, Renderer}};
+use wasm_bindgen::JsValue;
+
+/// A node that an application can be mounted to, either directly or through a CSS selector.
+pub trait MountTarget<R: Renderer = DefaultRenderer> {
+    fn resolve (self) -> Result<R::Element, JsValue>;
+}
+
+/// An application mounted by [`mount_to`] or [`hydrate`].
+///
+/// Dropping the handle unmounts the application, just like [`unmount`](Mount::unmount) does.
+#[must_use = "dropping the handle unmounts the application"]
+pub struct Mount<R: Renderer = DefaultRenderer> {
+    element: Element<R>,
+    /// Contents of the target before the application was mounted
+    original: Vec<R::Node>,
+    /// Whether the application was already unmounted, so that it isn't unmounted again once dropped
+    restored: bool,
+}
+
+/// Mounts `root` into `target`, taking over its current contents until the application is unmounted.
+///
+/// # Example
+/// ```rust,no_run
+/// use spiderweb::{client, dom::mount_to};
+///
+/// let app = client! { <main>{"Hello, world!"}</main> }.unwrap();
+/// mount_to("#app", app).unwrap().forget();
+/// ```
+pub fn mount_to<R: Renderer> (target: impl MountTarget<R>, root: impl Into<Child<R>>) -> Result<Mount<R>, JsValue> {
+    let target = target.resolve()?;
+
+    let mut original = Vec::new();
+    while let Some(node) = R::first_child(target.as_ref()) {
+        R::remove_child(target.as_ref(), &node)?;
+        original.push(node);
+    }
+
+    let element = Element::wrap(target);
+    let mount = Mount { element, original, restored: false };
+    mount.element.append_child(root)?;
+    return Ok(mount);
+}
+
+/// Mounts the application built by `f` into `target`, adopting the markup already inside of it (e.g. rendered
+/// by the server) instead of creating new nodes.
+///
+/// Listeners and bindings are attached to the adopted nodes. In debug builds, differences between the
+/// markup and the application are reported to the console, and the application's structure takes priority.
+/// Renderers that can't adopt nodes replace the markup instead (see [`Renderer::hydrate`]).
+///
+/// Unmounting the application puts the nodes that were inside of the target back, like [`mount_to`] does.
+///
+/// # Example
+/// ```rust,no_run
+/// use spiderweb::{client, dom::hydrate, state::Writeable};
+///
+/// let count = Writeable::new(0);
+/// hydrate("#app", || client! {
+///     <button on:click={move || count.update(|x| *x += 1)}>{"Increment"}</button>
+/// }).unwrap().forget();
+/// ```
+pub fn hydrate<R: Renderer, C: Into<Child<R>>> (target: impl MountTarget<R>, f: impl FnOnce() -> Result<C, JsValue>) -> Result<Mount<R>, JsValue> {
+    let target = target.resolve()?;
+
+    let mut original = Vec::new();
+    let mut next = R::first_child(target.as_ref());
+    while let Some(node) = next {
+        next = R::next_sibling(&node);
+        original.push(node);
+    }
+
+    let element = Element::wrap(target.clone());
+    R::hydrate(&target, || {
+        element.append_child(f()?)?;
+        Ok::<_, JsValue>(())
+    })?;
+
+    return Ok(Mount { element, original, restored: false });
+}
+
+impl<R: Renderer> Mount<R> {
+    /// Returns the element the application was mounted to.
+    #[inline]
+    pub fn element (&self) -> &Element<R> {
+        &self.element
+    }
+
+    /// Removes the application, dropping it alongside its listeners, bindings and hooks, and restores the
+    /// original contents of the target.
+    #[inline]
+    pub fn unmount (mut self) -> Result<(), JsValue> {
+        self.restore()
+    }
+
+    /// Keeps the application mounted for the rest of the program.
+    #[inline]
+    pub fn forget (self) {
+        core::mem::forget(self)
+    }
+
+    fn restore (&mut self) -> Result<(), JsValue> {
+        if core::mem::replace(&mut self.restored, true) {
+            return Ok(());
+        }
+        self.element.clear()?;
+
+        let target = self.element.html_node().as_ref();
+        for node in self.original.drain(..) {
+            R::append_child(target, &node)?;
+        }
+        return Ok(());
+    }
+}
+
+impl<R: Renderer> Drop for Mount<R> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Err(e) = self.restore() {
+            crate::eprintln!(&e)
+        }
+    }
+}
+
+impl<R: Renderer> MountTarget<R> for &str {
+    #[inline]
+    fn resolve (self) -> Result<R::Element, JsValue> {
+        R::query_selector(self)?
+            .ok_or_else(|| crate::error_value(&format!("no element matches '{self}'")))
+    }
+}
+
+impl<R: Renderer> MountTarget<R> for &String {
+    #[inline]
+    fn resolve (self) -> Result<R::Element, JsValue> {
+        MountTarget::<R>::resolve(self.as_str())
+    }
+}
+
+impl MountTarget for HtmlElement {
+    #[inline]
+    fn resolve (self) -> Result<HtmlElement, JsValue> {
+        Ok(self)
+    }
+}
+
+impl MountTarget for &HtmlElement {
+    #[inline]
+    fn resolve (self) -> Result<HtmlElement, JsValue> {
+        Ok(self.clone())
+    }
+}
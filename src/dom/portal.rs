@@ -0,0 +1,91 @@
+use super::{element::{Child, Element}, renderer::{DefaultRenderer, Renderer}};
+use wasm_bindgen::JsValue;
+
+/// A child whose content is physically placed somewhere else in the document (e.g. at the end of the body),
+/// while still being owned by its parent.
+///
+/// Listeners, bindings, context and lifecycle hooks of the content follow the parent, but the content itself
+/// escapes the parent's layout (e.g. `overflow: hidden`), which makes portals useful for modals and tooltips.
+/// The content is only placed into the target while the parent is attached to the document.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::dom::{Element, element::body, Portal};
+///
+/// let card = body().append_child_shared(Element::new("div")).unwrap();
+/// let modal = card.append_child(Portal::new(Element::new("dialog"))).unwrap();
+/// modal.add_text("Rendered at the end of the body").unwrap();
+/// ```
+pub struct Portal<R: Renderer = DefaultRenderer> {
+    pub(super) content: Element<R>,
+    /// Marks the position of the portal inside of its parent
+    pub(super) anchor: R::Node,
+    target: R::Node,
+}
+
+impl<R: Renderer> Portal<R> {
+    /// Creates a portal that places `content` at the end of the renderer's root (e.g. the document's body).
+    #[inline]
+    pub fn new (content: impl Into<Child<R>>) -> Self {
+        let target = R::root();
+        return Self::with_target(target.as_ref().clone(), content);
+    }
+
+    /// Creates a portal that places `content` at the end of the first element matching `selector`.
+    #[inline]
+    pub fn to_selector (selector: &str, content: impl Into<Child<R>>) -> Result<Self, JsValue> {
+        let target = R::query_selector(selector)?
+            .ok_or_else(|| crate::error_value(&format!("no element matches '{selector}'")))?;
+        return Ok(Self::with_target(target.as_ref().clone(), content));
+    }
+
+    #[inline]
+    fn with_target (target: R::Node, content: impl Into<Child<R>>) -> Self {
+        let content = match content.into() {
+            Child::Element(x) => x,
+            // Components and nested portals are wrapped, so that they keep their own ownership
+            child => {
+                let wrapper = Element::create("div");
+                let _ = wrapper.append_child(child);
+                wrapper
+            }
+        };
+
+        return Self {
+            content,
+            anchor: R::create_anchor(),
+            target,
+        };
+    }
+
+    /// Places the content into the target.
+    #[inline]
+    pub(super) fn attach (&self) {
+        if let Err(e) = R::append_child(&self.target, self.content_node()) {
+            crate::eprintln!(&e)
+        }
+    }
+
+    /// Removes the content from the target.
+    #[inline]
+    pub(super) fn detach (&self) {
+        let node = self.content_node();
+        if R::parent_node(node).as_ref() == Some(&self.target) {
+            if let Err(e) = R::remove_child(&self.target, node) {
+                crate::eprintln!(&e)
+            }
+        }
+    }
+
+    #[inline]
+    fn content_node (&self) -> &R::Node {
+        self.content.html_node().as_ref()
+    }
+}
+
+impl<R: Renderer> Drop for Portal<R> {
+    #[inline]
+    fn drop(&mut self) {
+        self.detach()
+    }
+}
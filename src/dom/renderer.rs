@@ -0,0 +1,242 @@
+use super::{attribute::Property, backend::{Callback, Comment, HtmlElement, Node, Text}, element::DOCUMENT, event::{Event, ListenerOptions}, hydration};
+use wasm_bindgen::JsValue;
+
+/// The renderer used by [`Element`](super::Element) unless another one is specified: the browser's DOM in
+/// WebAssembly, and an in-memory document elsewhere.
+#[cfg(target_family = "wasm")]
+pub type DefaultRenderer = super::web::Dom;
+/// The renderer used by [`Element`](super::Element) unless another one is specified: the browser's DOM in
+/// WebAssembly, and an in-memory document elsewhere.
+#[cfg(not(target_family = "wasm"))]
+pub type DefaultRenderer = super::memory::Memory;
+
+/// A backend that elements are rendered to, such as the browser's DOM.
+///
+/// Renderers own a tree of nodes, which [`Element`](super::Element) creates and rearranges as the state of the
+/// application changes. Every operation is an associated function, so renderers that need any state (e.g. a
+/// scene graph) keep it inside of their nodes, or in thread-local storage.
+///
+/// Components can target any renderer by being generic over it, and building their elements with
+/// `client!(R => ...)`.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::{client, component, dom::{Element, Renderer}};
+/// use wasm_bindgen::JsValue;
+///
+/// #[component]
+/// fn Badge<R: Renderer>() -> Result<Element<R>, JsValue> {
+///     client!(R => <span class={"badge"}>{"New"}</span>)
+/// }
+/// ```
+pub trait Renderer: 'static + Sized {
+    /// Any node of the tree
+    type Node: 'static + Clone + PartialEq;
+    /// A node that can hold children, attributes and listeners
+    type Element: 'static + Clone + AsRef<Self::Node>;
+    /// A node that displays text
+    type Text: 'static + Clone + AsRef<Self::Node>;
+    /// A registered event listener, which is passed back to the renderer to be removed
+    type Listener: 'static;
+
+    fn create_element (tag: &str) -> Self::Element;
+    fn create_text (s: &str) -> Self::Text;
+    /// Creates an invisible node that marks a position inside of its parent (e.g. the end of a list).
+    fn create_anchor () -> Self::Node;
+    fn set_text (text: &Self::Text, s: &str);
+
+    fn append_child (parent: &Self::Node, child: &Self::Node) -> Result<(), JsValue>;
+    /// Inserts `child` right before `reference`, or at the end of `parent` if there's no reference.
+    fn insert_before (parent: &Self::Node, child: &Self::Node, reference: Option<&Self::Node>) -> Result<(), JsValue>;
+    fn remove_child (parent: &Self::Node, child: &Self::Node) -> Result<(), JsValue>;
+    /// Replaces `old` with `child`.
+    fn replace_child (parent: &Self::Node, child: &Self::Node, old: &Self::Node) -> Result<(), JsValue>;
+    /// Removes every child of `parent`.
+    fn clear (parent: &Self::Node);
+
+    fn parent_node (node: &Self::Node) -> Option<Self::Node>;
+    fn first_child (node: &Self::Node) -> Option<Self::Node>;
+    fn next_sibling (node: &Self::Node) -> Option<Self::Node>;
+    /// Returns `true` if the node is currently displayed (e.g. attached to the document).
+    fn is_connected (node: &Self::Node) -> bool;
+
+    fn set_attribute (element: &Self::Element, name: &str, value: &str) -> Result<(), JsValue>;
+    fn remove_attribute (element: &Self::Element, name: &str) -> Result<(), JsValue>;
+    fn set_property (element: &Self::Element, name: &str, value: &Property) -> Result<(), JsValue>;
+    /// Returns the current value of the property `name` of the element (e.g. the `value` of an `<input>`), or the
+    /// value last set with [`set_property`](Renderer::set_property) if it doesn't change on its own.
+    ///
+    /// Two-way bindings read their element back through this method, so renderers without properties return
+    /// `None`, and their elements can only be bound in one direction.
+    fn get_property (element: &Self::Element, name: &str) -> Option<Property>;
+    /// Adds the class `name` to the element if `force` is `true`, and removes it otherwise.
+    fn toggle_class (element: &Self::Element, name: &str, force: bool) -> Result<(), JsValue>;
+    /// Sets the CSS property `prop` of the element's inline style.
+    fn set_style (element: &Self::Element, prop: &str, value: &str) -> Result<(), JsValue>;
+
+    /// Invokes `f` every time `event` is dispatched to the element.
+    fn add_listener (element: &Self::Element, event: &str, f: Box<dyn FnMut(Event)>, options: &ListenerOptions) -> Self::Listener;
+    fn remove_listener (element: &Self::Element, event: &str, listener: Self::Listener, capture: bool);
+
+    /// Returns the element that content is rendered into by default (e.g. the document's body).
+    fn root () -> Self::Element;
+
+    /// Returns the first element matching the CSS `selector`, if any.
+    ///
+    /// By default, renderers don't support selectors, and never find any element.
+    #[inline]
+    fn query_selector (selector: &str) -> Result<Option<Self::Element>, JsValue> {
+        let _ = selector;
+        return Ok(None);
+    }
+
+    /// Invoked once an element and all of its content have been created.
+    #[inline]
+    fn finish (element: &Self::Element) {
+        let _ = element;
+    }
+
+    /// Runs `f` while adopting the existing children of `target` (e.g. rendered by the server), instead of
+    /// creating new nodes.
+    ///
+    /// By default, renderers can't adopt nodes, so the children of `target` are removed and `f` creates new ones.
+    #[inline]
+    fn hydrate<U> (target: &Self::Element, f: impl FnOnce() -> U) -> U {
+        Self::clear(target.as_ref());
+        return f();
+    }
+}
+
+/* DEFAULT RENDERER */
+/// Nodes are adopted from the existing markup while hydrating, instead of being created.
+impl Renderer for DefaultRenderer {
+    type Node = Node;
+    type Element = HtmlElement;
+    type Text = Text;
+    type Listener = Callback;
+
+    #[inline]
+    fn create_element (tag: &str) -> HtmlElement {
+        hydration::element(tag).unwrap_or_else(|| DOCUMENT.with(|doc| doc.create_element(tag)))
+    }
+
+    #[inline]
+    fn create_text (s: &str) -> Text {
+        hydration::text(s).unwrap_or_else(|| Text::new(s))
+    }
+
+    #[inline]
+    fn create_anchor () -> Node {
+        hydration::anchor().unwrap_or_else(|| Comment::new("")).into()
+    }
+
+    #[inline]
+    fn set_text (text: &Text, s: &str) {
+        text.set_data(s)
+    }
+
+    #[inline]
+    fn append_child (parent: &Node, child: &Node) -> Result<(), JsValue> {
+        hydration::append_node(parent, child)
+    }
+
+    #[inline]
+    fn insert_before (parent: &Node, child: &Node, reference: Option<&Node>) -> Result<(), JsValue> {
+        parent.insert_before(child, reference).map(drop)
+    }
+
+    #[inline]
+    fn remove_child (parent: &Node, child: &Node) -> Result<(), JsValue> {
+        parent.remove_child(child).map(drop)
+    }
+
+    #[inline]
+    fn replace_child (parent: &Node, child: &Node, old: &Node) -> Result<(), JsValue> {
+        parent.replace_child(child, old).map(drop)
+    }
+
+    #[inline]
+    fn clear (parent: &Node) {
+        parent.set_text_content("")
+    }
+
+    #[inline]
+    fn parent_node (node: &Node) -> Option<Node> {
+        node.parent_node()
+    }
+
+    #[inline]
+    fn first_child (node: &Node) -> Option<Node> {
+        node.first_child()
+    }
+
+    #[inline]
+    fn next_sibling (node: &Node) -> Option<Node> {
+        node.next_sibling()
+    }
+
+    #[inline]
+    fn is_connected (node: &Node) -> bool {
+        node.is_connected()
+    }
+
+    #[inline]
+    fn set_attribute (element: &HtmlElement, name: &str, value: &str) -> Result<(), JsValue> {
+        element.set_attribute(name, value)
+    }
+
+    #[inline]
+    fn remove_attribute (element: &HtmlElement, name: &str) -> Result<(), JsValue> {
+        element.remove_attribute(name)
+    }
+
+    #[inline]
+    fn set_property (element: &HtmlElement, name: &str, value: &Property) -> Result<(), JsValue> {
+        element.set_property(name, value)
+    }
+
+    #[inline]
+    fn toggle_class (element: &HtmlElement, name: &str, force: bool) -> Result<(), JsValue> {
+        element.toggle_class(name, force)
+    }
+
+    #[inline]
+    fn set_style (element: &HtmlElement, prop: &str, value: &str) -> Result<(), JsValue> {
+        element.set_style(prop, value)
+    }
+
+    #[inline]
+    fn add_listener (element: &HtmlElement, event: &str, f: Box<dyn FnMut(Event)>, options: &ListenerOptions) -> Callback {
+        element.listen(event, f, options)
+    }
+
+    #[inline]
+    fn remove_listener (element: &HtmlElement, event: &str, listener: Callback, capture: bool) {
+        element.unlisten(event, &listener, capture)
+    }
+
+    #[inline]
+    fn root () -> HtmlElement {
+        DOCUMENT.with(|doc| doc.body())
+    }
+
+    #[inline]
+    fn query_selector (selector: &str) -> Result<Option<HtmlElement>, JsValue> {
+        DOCUMENT.with(|doc| doc.query_selector(selector))
+    }
+
+    #[inline]
+    fn get_property (element: &HtmlElement, name: &str) -> Option<Property> {
+        element.get_property(name)
+    }
+
+    #[inline]
+    fn finish (element: &HtmlElement) {
+        hydration::finish(element)
+    }
+
+    #[inline]
+    fn hydrate<U> (target: &HtmlElement, f: impl FnOnce() -> U) -> U {
+        hydration::hydrate_with(target, f)
+    }
+}
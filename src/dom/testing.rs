@@ -0,0 +1,98 @@
+//! Helpers to test components with `cargo test`, against the in-memory document.
+//!
+//! Elements are rendered into a document owned by the current thread, so every test gets its own. Nodes can be
+//! found with [`query_selector`](super::element::Node::query_selector), and interacted with by dispatching
+//! events (e.g. with [`click`](super::element::HtmlElement::click) or
+//! [`dispatch_event`](super::element::Node::dispatch_event)).
+//!
+//! # Example
+//! ```rust
+//! use spiderweb::{client, dom::testing::{assert_html, mount}, state::Writeable};
+//! use std::rc::Rc;
+//!
+//! let count = Rc::new(Writeable::new(0));
+//! let counter = count.clone();
+//! let label = count.map(|x| x.to_string());
+//! let app = mount(client! {
+//!     <div>
+//!         <button on:click={move || counter.update(|x| *x += 1)}>{"Increment"}</button>
+//!         <output>{&label}</output>
+//!     </div>
+//! }.unwrap()).unwrap();
+//!
+//! let button = app.element().html_node().query_selector("button").unwrap().unwrap();
+//! button.click();
+//! button.click();
+//!
+//! assert_html(app.element(), r#"
+//!     <body><div>
+//!         <button>Increment</button>
+//!         <output>2</output>
+//!     </div></body>
+//! "#);
+//! ```
+
+use super::{element::{body, Child}, mount::{mount_to, Mount}, Element};
+use wasm_bindgen::JsValue;
+
+/// Mounts `root` to the body of the document, so that it's attached (and its mount hooks are invoked) like it
+/// would be in the browser.
+#[inline]
+pub fn mount (root: impl Into<Child>) -> Result<Mount, JsValue> {
+    mount_to(body().html_node().clone(), root)
+}
+
+/// Runs the futures spawned in the background (e.g. the loaders of routes) until none of them can make
+/// progress.
+///
+/// Outside of WebAssembly, there's no event loop that drives them, so they only run when this is called.
+#[inline]
+pub fn run_until_stalled () {
+    crate::run_until_stalled()
+}
+
+/// Returns the message of the last error created by this crate on the current thread, taking it.
+///
+/// Outside of WebAssembly, errors are `undefined` (JavaScript values can't be created), so their message is kept
+/// aside instead.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::dom::{testing::take_error, Portal, Element};
+///
+/// assert!(Portal::to_selector("#missing", Element::new("aside")).is_err());
+/// assert_eq!(take_error().as_deref(), Some("no element matches '#missing'"));
+/// ```
+#[inline]
+pub fn take_error () -> Option<String> {
+    crate::take_error()
+}
+
+/// Asserts that `element` serialises to `expected`.
+///
+/// Whitespace between the tags of `expected` is ignored if it spans several lines, so markup can be indented.
+#[track_caller]
+pub fn assert_html (element: &Element, expected: &str) {
+    assert_eq!(element.to_html(), normalize(expected));
+}
+
+/// Removes the whitespace surrounding `expected`, and the multi-line whitespace between its tags.
+fn normalize (expected: &str) -> String {
+    let mut result = String::with_capacity(expected.len());
+    let mut rest = expected.trim();
+
+    while let Some(idx) = rest.find('>') {
+        let (tag, tail) = rest.split_at(idx + 1);
+        result.push_str(tag);
+
+        let whitespace = tail.len() - tail.trim_start().len();
+        let (gap, tail) = tail.split_at(whitespace);
+        if !(gap.contains('\n') && tail.starts_with('<')) {
+            result.push_str(gap);
+        }
+        rest = tail;
+    }
+
+    result.push_str(rest);
+    return result;
+}
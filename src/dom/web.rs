@@ -0,0 +1,235 @@
+use super::{attribute::Property, event::{Event, ListenerOptions}};
+use crate::sync::abort::AbortSignal;
+use js_sys::Function;
+use wasm_bindgen::{prelude::{wasm_bindgen, Closure}, JsCast, JsValue};
+
+/// Renderer of the browser's DOM
+#[derive(Debug, Clone, Copy)]
+pub struct Dom;
+
+/// Callback of an event listener, kept alive for as long as the listener is registered
+pub(super) type Callback = Closure<dyn FnMut(Event)>;
+
+#[wasm_bindgen]
+extern "C" {
+    pub type Window;
+    pub type Document;
+
+    /// An HTML element of the document
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = Node, extends = EventTarget, js_name = HTMLElement)]
+    pub type HtmlElement;
+
+    /// A text node of the document
+    #[derive(Clone)]
+    #[wasm_bindgen(extends = Node)]
+    pub type Text;
+
+    #[derive(Clone)]
+    #[wasm_bindgen(extends = Node)]
+    pub(super) type Comment;
+
+    /// A node of the document
+    #[derive(Debug, Clone, PartialEq)]
+    #[wasm_bindgen(extends = EventTarget)]
+    pub type Node;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub type EventTarget;
+
+    type DomTokenList;
+    type CssStyleDeclaration;
+    type JsListenerOptions;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn document(this: &Window) -> Document;
+
+    #[wasm_bindgen(structural, method, getter)]
+    pub(super) fn body(this: &Document) -> HtmlElement;
+    #[wasm_bindgen(structural, method, js_name = createElement)]
+    pub(super) fn create_element(this: &Document, tag: &str) -> HtmlElement;
+    #[wasm_bindgen(structural, method, catch, js_name = querySelector)]
+    pub(super) fn query_selector(this: &Document, selector: &str) -> Result<Option<HtmlElement>, JsValue>;
+
+    #[wasm_bindgen(structural, method, catch, js_name = appendChild)]
+    pub(super) fn append_child (this: &Node, child: &Node) -> Result<Node, JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = removeChild)]
+    pub(super) fn remove_child (this: &Node, child: &Node) -> Result<Node, JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = insertBefore)]
+    pub(super) fn insert_before (this: &Node, child: &Node, reference: Option<&Node>) -> Result<Node, JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = replaceChild)]
+    pub(super) fn replace_child (this: &Node, child: &Node, old: &Node) -> Result<Node, JsValue>;
+    #[wasm_bindgen(structural, method, setter, js_name = textContent)]
+    pub(super) fn set_text_content (this: &Node, s: &str);
+    #[wasm_bindgen(structural, method, getter, js_name = parentNode)]
+    pub(super) fn parent_node (this: &Node) -> Option<Node>;
+    #[wasm_bindgen(structural, method, getter, js_name = nodeType)]
+    pub(super) fn node_type (this: &Node) -> u16;
+    #[wasm_bindgen(structural, method, getter, js_name = nodeName)]
+    pub(super) fn node_name (this: &Node) -> String;
+    #[wasm_bindgen(structural, method, getter, js_name = firstChild)]
+    pub(super) fn first_child (this: &Node) -> Option<Node>;
+    #[wasm_bindgen(structural, method, getter, js_name = isConnected)]
+    pub(super) fn is_connected (this: &Node) -> bool;
+    #[wasm_bindgen(structural, method, getter, js_name = nextSibling)]
+    pub(super) fn next_sibling (this: &Node) -> Option<Node>;
+
+    #[wasm_bindgen(structural, method, catch, js_name = setAttribute)]
+    pub(super) fn set_attribute (this: &HtmlElement, name: &str, value: &str) -> Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = removeAttribute)]
+    pub(super) fn remove_attribute (this: &HtmlElement, name: &str) -> Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, getter, js_name = outerHTML)]
+    pub(super) fn outer_html (this: &HtmlElement) -> String;
+    #[wasm_bindgen(structural, method, getter, js_name = classList)]
+    fn class_list (this: &HtmlElement) -> DomTokenList;
+    #[wasm_bindgen(structural, method, getter)]
+    fn style (this: &HtmlElement) -> CssStyleDeclaration;
+
+    #[wasm_bindgen(structural, method, catch)]
+    fn toggle (this: &DomTokenList, token: &str, force: bool) -> Result<bool, JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = setProperty)]
+    fn set_property (this: &CssStyleDeclaration, name: &str, value: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(constructor)]
+    pub(super) fn new (s: &str) -> Text;
+    #[wasm_bindgen(structural, method, getter)]
+    pub(super) fn data (this: &Text) -> String;
+    #[wasm_bindgen(structural, method, setter, js_name = data)]
+    pub(super) fn set_data (this: &Text, s: &str);
+    #[wasm_bindgen(structural, method, js_name = splitText)]
+    pub(super) fn split_text (this: &Text, offset: u32) -> Text;
+
+    #[wasm_bindgen(constructor)]
+    pub(super) fn new (s: &str) -> Comment;
+
+    #[wasm_bindgen(structural, method, js_name = addEventListener)]
+    fn add_event_listener (this: &EventTarget, event: &str, f: &Function, options: &JsListenerOptions);
+    #[wasm_bindgen(structural, method, js_name = removeEventListener)]
+    fn remove_event_listener (this: &EventTarget, event: &str, f: &Function, capture: bool);
+
+    #[wasm_bindgen(structural, method, setter)]
+    fn set_once (this: &JsListenerOptions, value: bool);
+    #[wasm_bindgen(structural, method, setter)]
+    fn set_passive (this: &JsListenerOptions, value: bool);
+    #[wasm_bindgen(structural, method, setter)]
+    fn set_capture (this: &JsListenerOptions, value: bool);
+    #[wasm_bindgen(structural, method, setter)]
+    fn set_signal (this: &JsListenerOptions, value: &AbortSignal);
+}
+
+impl Node {
+    /// Casts the node into an element, without checking its type.
+    #[inline]
+    pub(super) fn into_element (self) -> HtmlElement {
+        self.unchecked_into()
+    }
+
+    /// Casts the node into a text node, without checking its type.
+    #[inline]
+    pub(super) fn into_text (self) -> Text {
+        self.unchecked_into()
+    }
+
+    /// Casts the node into a comment, without checking its type.
+    #[inline]
+    pub(super) fn into_comment (self) -> Comment {
+        self.unchecked_into()
+    }
+}
+
+impl HtmlElement {
+    /// Adds the class `name` if `force` is `true`, and removes it otherwise.
+    #[inline]
+    pub(super) fn toggle_class (&self, name: &str, force: bool) -> Result<(), JsValue> {
+        self.class_list().toggle(name, force).map(drop)
+    }
+
+    /// Sets the CSS property `prop` of the element's inline style.
+    #[inline]
+    pub(super) fn set_style (&self, prop: &str, value: &str) -> Result<(), JsValue> {
+        self.style().set_property(prop, value)
+    }
+
+    /// Sets a property of the underlying JavaScript object.
+    #[inline]
+    pub(super) fn set_property (&self, name: &str, value: &Property) -> Result<(), JsValue> {
+        js_sys::Reflect::set(self, &JsValue::from_str(name), &value.to_js())?;
+        return Ok(())
+    }
+
+    /// Returns the value of a property of the underlying JavaScript object, if it's defined.
+    #[inline]
+    pub(super) fn get_property (&self, name: &str) -> Option<Property> {
+        let value = js_sys::Reflect::get(self, &JsValue::from_str(name)).ok()?;
+        return Property::from_js(value)
+    }
+}
+
+impl EventTarget {
+    /// Registers `f` as a listener of `event`, returning the callback needed to remove it.
+    #[inline]
+    pub(super) fn listen (&self, event: &str, f: Box<dyn FnMut(Event)>, options: &ListenerOptions) -> Callback {
+        let f = Closure::wrap(f);
+        self.add_event_listener(event, f.as_ref().unchecked_ref(), &options.to_js());
+        return f
+    }
+
+    #[inline]
+    pub(super) fn unlisten (&self, event: &str, f: &Callback, capture: bool) {
+        self.remove_event_listener(event, f.as_ref().unchecked_ref(), capture)
+    }
+}
+
+impl Property {
+    #[inline]
+    fn to_js (&self) -> JsValue {
+        match self {
+            Self::Null => JsValue::NULL,
+            Self::Bool(x) => JsValue::from_bool(*x),
+            Self::Number(x) => JsValue::from_f64(*x),
+            Self::String(x) => JsValue::from_str(x),
+            Self::Js(x) => x.clone(),
+        }
+    }
+
+    #[inline]
+    fn from_js (value: JsValue) -> Option<Self> {
+        if value.is_undefined() {
+            return None
+        } else if value.is_null() {
+            return Some(Self::Null)
+        }
+
+        return Some(match (value.as_bool(), value.as_f64(), value.as_string()) {
+            (Some(x), _, _) => Self::Bool(x),
+            (_, Some(x), _) => Self::Number(x),
+            (_, _, Some(x)) => Self::String(x),
+            _ => Self::Js(value),
+        })
+    }
+}
+
+impl ListenerOptions {
+    #[inline]
+    fn to_js (&self) -> JsListenerOptions {
+        let options = js_sys::Object::new().unchecked_into::<JsListenerOptions>();
+        options.set_once(self.once);
+        options.set_passive(self.passive);
+        options.set_capture(self.capture);
+        if let Some(ref signal) = self.signal {
+            options.set_signal(signal);
+        }
+        return options
+    }
+}
+
+#[inline]
+pub fn window () -> Window {
+    use crate::wasm_bindgen::UnwrapThrowExt;
+    JsCast::dyn_into(js_sys::global()).unwrap_throw()
+}
+
+#[inline]
+pub(super) fn document () -> Document {
+    window().document()
+}
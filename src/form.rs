@@ -0,0 +1,567 @@
+use crate::{dom::{Element, Renderer}, state::{Readable, Subscription, Writeable}};
+use futures::{future::{join_all, AbortHandle, LocalBoxFuture}, Future, FutureExt};
+use std::{cell::{Cell, UnsafeCell}, rc::{Rc, Weak}};
+use wasm_bindgen::JsValue;
+
+type Validator<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+type AsyncValidator<T> = Box<dyn Fn(T) -> LocalBoxFuture<'static, Result<(), String>>>;
+type SubmitHandler = Box<dyn Fn() -> LocalBoxFuture<'static, Result<(), String>>>;
+
+/// A field of a form, holding its value alongside whether it's been modified or visited, and the message of its
+/// first failed validator.
+///
+/// Fields are validated every time their value changes, and every time they're [touched](Field::touch).
+/// Synchronous validators run first, and asynchronous ones only run if all of them succeed. If the value changes
+/// while an asynchronous validation is in progress, it's aborted, so validators can be debounced by waiting on a
+/// [`Timeout`](crate::time::Timeout) before doing any work.
+///
+/// Fields are cheap to clone, with every clone sharing the same state.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::form::Field;
+///
+/// let name = Field::new(String::new())
+///     .with_validator(|x: &String| match x.is_empty() {
+///         true => Err(String::from("Name is required")),
+///         false => Ok(()),
+///     });
+///
+/// name.value().set(String::from("Alex"));
+/// assert!(name.is_dirty().get());
+/// assert_eq!(name.error().with(Clone::clone), None);
+///
+/// name.value().set(String::new());
+/// assert_eq!(name.error().with(Clone::clone).as_deref(), Some("Name is required"));
+/// ```
+pub struct Field<T> {
+    inner: Rc<FieldInner<T>>,
+}
+
+struct FieldInner<T> {
+    value: Rc<Writeable<T>>,
+    initial: UnsafeCell<T>,
+    dirty: Writeable<bool>,
+    touched: Writeable<bool>,
+    validating: Writeable<bool>,
+    /// Whether the current value finished validating, in which case `error` holds the result
+    validated: Writeable<bool>,
+    error: Writeable<Option<String>>,
+    validators: UnsafeCell<Vec<Validator<T>>>,
+    async_validators: UnsafeCell<Vec<AsyncValidator<T>>>,
+    /// Incremented every time a validation starts, so that outdated ones don't overwrite the result
+    generation: Cell<u64>,
+    /// Handle of the validation running in the background, if any
+    pending: Cell<Option<AbortHandle>>,
+    subscription: UnsafeCell<Option<Subscription>>,
+}
+
+/// A set of fields, submitted together once all of them are valid.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::form::{Field, Form};
+///
+/// let email = Field::new(String::new())
+///     .with_validator(|x: &String| x.contains('@').then_some(()).ok_or_else(|| String::from("Invalid email")));
+///
+/// let form = Form::new()
+///     .with_field(&email)
+///     .on_submit(|| async { Ok(()) });
+///
+/// # futures::executor::block_on(async {
+/// assert!(!form.submit().await);
+/// assert!(email.is_touched().get());
+///
+/// email.value().set(String::from("alex@example.com"));
+/// assert!(form.submit().await);
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Form {
+    inner: Rc<FormInner>,
+}
+
+struct FormInner {
+    fields: UnsafeCell<Vec<Rc<dyn AnyField>>>,
+    handler: UnsafeCell<Option<SubmitHandler>>,
+    submitting: Writeable<bool>,
+    error: Writeable<Option<String>>,
+}
+
+/// A value that form controls can be bound to.
+pub trait FieldValue: 'static + Clone + PartialEq {
+    /// Binds the matching property of `element` (e.g. `value` or `checked`) to `state` in both directions.
+    fn bind_to<R: Renderer> (element: &Element<R>, state: &Rc<Writeable<Self>>) -> Result<(), JsValue>;
+}
+
+/// Result of the synchronous part of a validation
+enum Validation {
+    Done (bool),
+    Pending (LocalBoxFuture<'static, bool>),
+}
+
+impl<T: 'static + Clone + PartialEq> Field<T> {
+    /// Creates a field whose value starts as `initial`.
+    pub fn new (initial: T) -> Self {
+        let inner = Rc::new(FieldInner {
+            value: Rc::new(Writeable::new(initial.clone())),
+            initial: UnsafeCell::new(initial),
+            dirty: Writeable::new(false),
+            touched: Writeable::new(false),
+            validating: Writeable::new(false),
+            validated: Writeable::new(false),
+            error: Writeable::new(None),
+            validators: UnsafeCell::new(Vec::new()),
+            async_validators: UnsafeCell::new(Vec::new()),
+            generation: Cell::new(0),
+            pending: Cell::new(None),
+            subscription: UnsafeCell::new(None),
+        });
+
+        let weak = Rc::downgrade(&inner);
+        let sub = inner.value.subscribe(move |x| {
+            if let Some(inner) = weak.upgrade() {
+                inner.dirty.set(*x != *unsafe { &*inner.initial.get() });
+                inner.validate_in_background();
+            }
+        });
+
+        unsafe { *inner.subscription.get() = Some(sub) };
+        return Self { inner };
+    }
+
+    /// Adds a validator, which returns the message to display if the value is invalid.
+    #[inline]
+    pub fn with_validator<F: 'static + Fn(&T) -> Result<(), String>> (self, f: F) -> Self {
+        unsafe { &mut *self.inner.validators.get() }.push(Box::new(f));
+        return self
+    }
+
+    /// Adds an asynchronous validator (e.g. one checking with a server that a username is available), which only
+    /// runs once every synchronous validator has succeeded.
+    #[inline]
+    pub fn with_async_validator<Fut, F> (self, f: F) -> Self
+    where
+        Fut: 'static + Future<Output = Result<(), String>>,
+        F: 'static + Fn(T) -> Fut,
+    {
+        unsafe { &mut *self.inner.async_validators.get() }.push(Box::new(move |x| f(x).boxed_local()));
+        return self
+    }
+
+    /// Returns the cell holding the value of the field.
+    #[inline]
+    pub fn value (&self) -> &Rc<Writeable<T>> {
+        &self.inner.value
+    }
+
+    /// Returns the message of the first failed validator, if any.
+    #[inline]
+    pub fn error (&self) -> &Readable<Option<String>> {
+        &self.inner.error
+    }
+
+    /// Returns whether the value differs from the initial one.
+    #[inline]
+    pub fn is_dirty (&self) -> &Readable<bool> {
+        &self.inner.dirty
+    }
+
+    /// Returns whether the field has been visited (e.g. focused and then left) by the user.
+    #[inline]
+    pub fn is_touched (&self) -> &Readable<bool> {
+        &self.inner.touched
+    }
+
+    /// Returns whether an asynchronous validation is in progress.
+    #[inline]
+    pub fn is_validating (&self) -> &Readable<bool> {
+        &self.inner.validating
+    }
+
+    /// Marks the field as touched, and validates it in the background.
+    #[inline]
+    pub fn touch (&self) {
+        self.inner.touched.set(true);
+        self.inner.validate_in_background();
+    }
+
+    /// Validates the current value, resolving to `true` if every validator succeeded.
+    #[inline]
+    pub fn validate (&self) -> impl Future<Output = bool> {
+        FieldInner::validate(&self.inner)
+    }
+
+    /// Restores the initial value of the field, clearing its error and marking it as pristine and untouched.
+    pub fn reset (&self) {
+        self.inner.value.set(unsafe { &*self.inner.initial.get() }.clone());
+        // Discard the validation started by the change
+        self.inner.abort();
+        self.inner.generation.set(self.inner.generation.get() + 1);
+
+        self.inner.dirty.set(false);
+        self.inner.touched.set(false);
+        self.inner.validating.set(false);
+        self.inner.validated.set(false);
+        self.inner.error.set(None);
+    }
+}
+
+impl<T: 'static + Clone + PartialEq> FieldInner<T> {
+    /// Runs the synchronous validators, returning the asynchronous ones as a future if all of them succeed.
+    fn start_validation (self: &Rc<Self>) -> Validation {
+        self.abort();
+        let generation = self.generation.get() + 1;
+        self.generation.set(generation);
+        self.validated.set(false);
+
+        let value = self.value.with(Clone::clone);
+        for validator in unsafe { &*self.validators.get() } {
+            if let Err(e) = validator(&value) {
+                self.validating.set(false);
+                self.error.set(Some(e));
+                self.validated.set(true);
+                return Validation::Done(false);
+            }
+        }
+
+        self.error.set(None);
+        let validators = unsafe { &*self.async_validators.get() };
+        if validators.is_empty() {
+            self.validating.set(false);
+            self.validated.set(true);
+            return Validation::Done(true);
+        }
+
+        let futures = validators.iter().map(|f| f(value.clone())).collect::<Vec<_>>();
+        let progress = Progress { field: Rc::downgrade(self), generation };
+        return Validation::Pending(async move {
+            if let Some(x) = progress.current() {
+                x.validating.set(true);
+            }
+
+            let mut result = Ok(());
+            for fut in futures {
+                if let Err(e) = fut.await {
+                    result = Err(e);
+                    break;
+                }
+            }
+
+            let valid = result.is_ok();
+            if let Some(x) = progress.current() {
+                x.error.set(result.err());
+                x.validated.set(true);
+            }
+            return valid;
+        }.boxed_local());
+    }
+
+    #[inline]
+    fn validate (self: &Rc<Self>) -> LocalBoxFuture<'static, bool> {
+        match self.start_validation() {
+            Validation::Done(x) => futures::future::ready(x).boxed_local(),
+            Validation::Pending(fut) => fut,
+        }
+    }
+
+    /// Validates the field, running the asynchronous validators in the background, where they're aborted as soon
+    /// as another validation starts.
+    fn validate_in_background (self: &Rc<Self>) {
+        if let Validation::Pending(fut) = self.start_validation() {
+            let (fut, handle) = futures::future::abortable(fut);
+            self.pending.set(Some(handle));
+            crate::spawn_local(fut.map(drop));
+        }
+    }
+
+    /// Returns the result of the last validation of the current value or, if it hasn't been validated yet, of the
+    /// synchronous validators. Values that still have to pass asynchronous validators aren't valid.
+    fn is_valid (&self) -> bool {
+        if self.validated.get() {
+            return self.error.with(Option::is_none)
+        }
+
+        let validators = unsafe { &*self.validators.get() };
+        return unsafe { &*self.async_validators.get() }.is_empty()
+            && self.value.with(|x| validators.iter().all(|f| f(x).is_ok()))
+    }
+
+    #[inline]
+    fn abort (&self) {
+        if let Some(handle) = self.pending.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl<T> Clone for Field<T> {
+    #[inline]
+    fn clone (&self) -> Self {
+        return Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Drop for FieldInner<T> {
+    #[inline]
+    fn drop (&mut self) {
+        if let Some(handle) = self.pending.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// An asynchronous validation, which clears the `validating` flag of its field once it completes or is dropped
+struct Progress<T> {
+    field: Weak<FieldInner<T>>,
+    generation: u64,
+}
+
+impl<T> Progress<T> {
+    /// Returns the field, unless it's been dropped or a newer validation has started.
+    #[inline]
+    fn current (&self) -> Option<Rc<FieldInner<T>>> {
+        self.field.upgrade().filter(|x| x.generation.get() == self.generation)
+    }
+}
+
+impl<T> Drop for Progress<T> {
+    #[inline]
+    fn drop (&mut self) {
+        if let Some(x) = self.current() {
+            x.validating.set(false);
+        }
+    }
+}
+
+/// A submission in progress, which clears the `submitting` flag of its form once it completes or is dropped
+struct Submission<'a> (&'a FormInner);
+
+impl Drop for Submission<'_> {
+    #[inline]
+    fn drop (&mut self) {
+        self.0.submitting.set(false);
+    }
+}
+
+impl Form {
+    #[inline]
+    pub fn new () -> Self {
+        return Self {
+            inner: Rc::new(FormInner {
+                fields: UnsafeCell::new(Vec::new()),
+                handler: UnsafeCell::new(None),
+                submitting: Writeable::new(false),
+                error: Writeable::new(None),
+            })
+        }
+    }
+
+    /// Adds `field` to the form.
+    #[inline]
+    pub fn with_field<T: 'static + Clone + PartialEq> (self, field: &Field<T>) -> Self {
+        unsafe { &mut *self.inner.fields.get() }.push(Rc::new(field.clone()));
+        return self
+    }
+
+    /// Sets the handler called once the form is submitted and every field is valid, which returns the message to
+    /// display if the submission failed.
+    #[inline]
+    pub fn on_submit<Fut, F> (self, f: F) -> Self
+    where
+        Fut: 'static + Future<Output = Result<(), String>>,
+        F: 'static + Fn() -> Fut,
+    {
+        unsafe { *self.inner.handler.get() = Some(Box::new(move || f().boxed_local())) };
+        return self
+    }
+
+    /// Returns the message of the last failed submission, if any.
+    #[inline]
+    pub fn error (&self) -> &Readable<Option<String>> {
+        &self.inner.error
+    }
+
+    /// Returns whether the form is being submitted.
+    #[inline]
+    pub fn is_submitting (&self) -> &Readable<bool> {
+        &self.inner.submitting
+    }
+
+    /// Returns whether any field differs from its initial value.
+    ///
+    /// The state of every field is read through its cells, so this is tracked when called inside of
+    /// [`computed`](crate::state::computed).
+    #[inline]
+    pub fn is_dirty (&self) -> bool {
+        self.fields().iter().any(|x| x.is_dirty())
+    }
+
+    /// Returns whether every field is valid, according to the last validation of its current value.
+    ///
+    /// Fields that haven't been validated yet (e.g. pristine ones) run their synchronous validators, and aren't
+    /// valid if they have asynchronous ones.
+    ///
+    /// The state of every field is read through its cells, so this is tracked when called inside of
+    /// [`computed`](crate::state::computed).
+    #[inline]
+    pub fn is_valid (&self) -> bool {
+        self.fields().iter().all(|x| x.is_valid())
+    }
+
+    /// Marks every field as touched and validates them, calling the submit handler if all of them are valid.
+    ///
+    /// Resolves to `true` if the form was submitted successfully. Submissions made while another one is in
+    /// progress are ignored.
+    pub fn submit (&self) -> impl Future<Output = bool> {
+        let inner = self.inner.clone();
+        return async move {
+            if inner.submitting.get() {
+                return false
+            }
+
+            inner.submitting.set(true);
+            inner.error.set(None);
+            let _submission = Submission(&inner);
+
+            let fields = unsafe { &*inner.fields.get() };
+            let validations = fields.iter().map(|x| {
+                x.touch();
+                x.validate()
+            }).collect::<Vec<_>>();
+
+            let mut result = join_all(validations).await.into_iter().all(|x| x);
+            let handler = unsafe { &*inner.handler.get() }.as_ref().filter(|_| result).map(|f| f());
+            if let Some(handler) = handler {
+                let submission = handler.await;
+                result = submission.is_ok();
+                inner.error.set(submission.err());
+            }
+
+            return result
+        }
+    }
+
+    /// Resets every field to its initial value, and clears the error of the last submission.
+    #[inline]
+    pub fn reset (&self) {
+        for field in self.fields() {
+            field.reset();
+        }
+        self.inner.error.set(None);
+    }
+
+    #[inline]
+    fn fields (&self) -> &[Rc<dyn AnyField>] {
+        unsafe { &*self.inner.fields.get() }
+    }
+}
+
+impl Default for Form {
+    #[inline]
+    fn default () -> Self {
+        Self::new()
+    }
+}
+
+/// Type-erased field of a [`Form`]
+trait AnyField {
+    /// Marks the field as touched, without validating it.
+    fn touch (&self);
+    fn validate (&self) -> LocalBoxFuture<'static, bool>;
+    fn reset (&self);
+    fn is_dirty (&self) -> bool;
+    fn is_valid (&self) -> bool;
+}
+
+impl<T: 'static + Clone + PartialEq> AnyField for Field<T> {
+    #[inline]
+    fn touch (&self) {
+        self.inner.touched.set(true)
+    }
+
+    #[inline]
+    fn validate (&self) -> LocalBoxFuture<'static, bool> {
+        FieldInner::validate(&self.inner)
+    }
+
+    #[inline]
+    fn reset (&self) {
+        Field::reset(self)
+    }
+
+    #[inline]
+    fn is_dirty (&self) -> bool {
+        self.inner.dirty.get()
+    }
+
+    #[inline]
+    fn is_valid (&self) -> bool {
+        self.inner.is_valid()
+    }
+}
+
+/* DOM */
+impl<R: Renderer> Element<R> {
+    /// Binds a form control to `field`, marking it as touched once the control loses focus.
+    ///
+    /// Text inputs are bound to `String` fields, checkboxes to `bool` fields and numeric inputs to fields of any
+    /// number.
+    pub fn bind_field<T: FieldValue> (&self, field: &Field<T>) -> Result<(), JsValue> {
+        T::bind_to(self, field.value())?;
+
+        let field = Rc::downgrade(&field.inner);
+        self.add_event_listener_boxed("blur", Box::new(move |_| {
+            if let Some(inner) = field.upgrade() {
+                Field { inner }.touch()
+            }
+        }));
+        return Ok(())
+    }
+
+    /// Submits `form` whenever the element (a `<form>`) is submitted, instead of navigating.
+    pub fn bind_form (&self, form: &Form) -> Result<(), JsValue> {
+        let form = Rc::downgrade(&form.inner);
+        self.add_event_listener_boxed("submit", Box::new(move |ev| {
+            ev.prevent_default();
+            if let Some(inner) = form.upgrade() {
+                crate::spawn_local(Form { inner }.submit().map(drop));
+            }
+        }));
+        return Ok(())
+    }
+}
+
+impl FieldValue for String {
+    #[inline]
+    fn bind_to<R: Renderer> (element: &Element<R>, state: &Rc<Writeable<Self>>) -> Result<(), JsValue> {
+        element.bind_value(state)
+    }
+}
+
+impl FieldValue for bool {
+    #[inline]
+    fn bind_to<R: Renderer> (element: &Element<R>, state: &Rc<Writeable<Self>>) -> Result<(), JsValue> {
+        element.bind_checked(state)
+    }
+}
+
+macro_rules! impl_field_value {
+    ($($t:ty),+) => {
+        $(
+            impl FieldValue for $t {
+                #[inline]
+                fn bind_to<R: Renderer> (element: &Element<R>, state: &Rc<Writeable<Self>>) -> Result<(), JsValue> {
+                    element.bind_number(state)
+                }
+            }
+        )+
+    };
+}
+
+impl_field_value! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64
+}
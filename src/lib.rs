@@ -1,7 +1,7 @@
 #![cfg_attr(feature = "nightly", feature(fn_traits, unboxed_closures, tuple_trait, trait_alias, downcast_unchecked, nonzero_ops, ptr_metadata, min_specialization))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-#[cfg(not(all(target_family = "wasm", not(target_feature = "atomics"))))]
+#[cfg(all(target_family = "wasm", target_feature = "atomics"))]
 compile_error!("Unsupported target");
 
 macro_rules! flat_mod {
@@ -38,6 +38,7 @@ macro_rules! dbg {
     };
 }
 
+#[cfg(target_family = "wasm")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 extern "C" {
     pub(crate) type WeakRef;
@@ -59,6 +60,43 @@ extern "C" {
     pub fn error(s: &wasm_bindgen::JsValue);
 }
 
+/// JavaScript values can't be inspected outside of WebAssembly, so they aren't logged.
+#[cfg(not(target_family = "wasm"))]
+#[doc(hidden)]
+#[inline]
+pub fn log (_: &wasm_bindgen::JsValue) {}
+
+/// JavaScript values can't be inspected outside of WebAssembly, so only the fact that an error occurred is logged,
+/// to the standard error. Errors created by this crate keep their message aside until it's retrieved with
+/// [`take_error`](dom::testing::take_error).
+#[cfg(not(target_family = "wasm"))]
+#[doc(hidden)]
+#[inline]
+pub fn error (_: &wasm_bindgen::JsValue) {
+    std::eprintln!("error: JavaScript values can't be displayed outside of WebAssembly");
+}
+
+/// Logs `s` to the console, or to the standard output outside of WebAssembly.
+#[doc(hidden)]
+#[inline]
+pub fn log_str (s: &str) {
+    #[cfg(target_family = "wasm")]
+    log(&wasm_bindgen::JsValue::from_str(s));
+    #[cfg(not(target_family = "wasm"))]
+    std::println!("{s}");
+}
+
+/// Logs `s` to the console as an error, or to the standard error outside of WebAssembly.
+#[doc(hidden)]
+#[inline]
+pub fn error_str (s: &str) {
+    #[cfg(target_family = "wasm")]
+    error(&wasm_bindgen::JsValue::from_str(s));
+    #[cfg(not(target_family = "wasm"))]
+    std::eprintln!("{s}");
+}
+
+#[cfg(target_family = "wasm")]
 impl WeakRef {
     #[inline]
     pub fn deref (&self) -> Option<wasm_bindgen::JsValue> {
@@ -82,12 +120,82 @@ pub mod dom;
 /// Cells designed to modify and propagate state.
 pub mod state;
 /// Task-related functionality
+#[cfg(target_family = "wasm")]
 pub mod task;
 /// Time-related functionality
 pub mod time;
 pub mod flag;
+/// Form state and validation
+pub mod form;
+/// Client-side routing
+pub mod router;
 
 pub use spiderweb_proc::*;
 
+#[cfg(target_family = "wasm")]
 #[inline(always)]
-pub(crate) fn noop() {}
\ No newline at end of file
+pub(crate) fn noop() {}
+
+/// Creates an error with the specified message.
+///
+/// JavaScript values can't be created outside of WebAssembly, so the returned value is `undefined`, and the
+/// message is kept by the current thread until it's retrieved with [`take_error`](dom::testing::take_error).
+#[inline]
+pub(crate) fn error_value (msg: &str) -> wasm_bindgen::JsValue {
+    #[cfg(target_family = "wasm")]
+    return wasm_bindgen::JsValue::from_str(msg);
+    #[cfg(not(target_family = "wasm"))]
+    {
+        LAST_ERROR.with(|x| *x.borrow_mut() = Some(msg.to_owned()));
+        return wasm_bindgen::JsValue::UNDEFINED;
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+thread_local! {
+    static LAST_ERROR: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Returns the message of the last error created on the current thread, if it hasn't been taken yet.
+#[cfg(not(target_family = "wasm"))]
+#[inline]
+pub(crate) fn take_error () -> Option<String> {
+    LAST_ERROR.with(|x| x.borrow_mut().take())
+}
+
+/// Runs `fut` in the background.
+///
+/// Like in the browser, the future doesn't start running until the current task yields. Outside of WebAssembly
+/// there's no event loop to yield to, so the future is queued on a pool owned by the current thread, which is run
+/// by [`run_until_stalled`](dom::testing::run_until_stalled).
+#[inline]
+pub(crate) fn spawn_local<F: 'static + std::future::Future<Output = ()>> (fut: F) {
+    #[cfg(target_family = "wasm")]
+    wasm_bindgen_futures::spawn_local(fut);
+    #[cfg(not(target_family = "wasm"))]
+    {
+        use futures::task::LocalSpawnExt;
+        LOCAL_POOL.with(|(_, spawner)| spawner.spawn_local(fut)).expect("the local pool is never shut down");
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+thread_local! {
+    static LOCAL_POOL: (std::cell::RefCell<futures::executor::LocalPool>, futures::executor::LocalSpawner) = {
+        let pool = futures::executor::LocalPool::new();
+        let spawner = pool.spawner();
+        (std::cell::RefCell::new(pool), spawner)
+    };
+}
+
+/// Runs the futures spawned in the background until none of them can make progress.
+///
+/// Futures spawned while the pool is already running are picked up by it.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn run_until_stalled () {
+    LOCAL_POOL.with(|(pool, _)| {
+        if let Ok(mut pool) = pool.try_borrow_mut() {
+            pool.run_until_stalled()
+        }
+    })
+}
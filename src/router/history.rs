@@ -0,0 +1,294 @@
+use super::Location;
+use std::{cell::{Cell, UnsafeCell}, rc::Rc};
+use wasm_bindgen::JsValue;
+#[cfg(target_family = "wasm")]
+use crate::dom::element::{window, Window};
+#[cfg(target_family = "wasm")]
+use js_sys::Function;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen::{prelude::{wasm_bindgen, Closure}, JsCast};
+
+/// A stack of locations that a [`Router`](super::Router) navigates through, such as the browser's session history.
+pub trait History: 'static {
+    /// Returns the current location.
+    fn location (&self) -> Location;
+    /// Adds `location` to the history, making it the current one.
+    fn push (&self, location: &Location) -> Result<(), JsValue>;
+    /// Replaces the current location with `location`.
+    fn replace (&self, location: &Location) -> Result<(), JsValue>;
+    /// Moves `delta` entries through the history (e.g. `-1` to go back), notifying the listener once the
+    /// location has changed.
+    fn go (&self, delta: isize) -> Result<(), JsValue>;
+    /// Invokes `f` whenever the current location changes by other means than [`push`](History::push) and
+    /// [`replace`](History::replace) (e.g. the user going back), replacing the previous listener.
+    fn listen (&self, f: Box<dyn FnMut()>);
+
+    /// Returns the `href` of links to `location`.
+    #[inline]
+    fn href (&self, location: &Location) -> String {
+        location.to_string()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+#[wasm_bindgen]
+extern "C" {
+    type JsLocation;
+    type JsHistory;
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn location (this: &Window) -> JsLocation;
+    #[wasm_bindgen(structural, method, getter)]
+    fn history (this: &Window) -> JsHistory;
+    #[wasm_bindgen(structural, method, js_name = addEventListener)]
+    fn add_event_listener (this: &Window, event: &str, f: &Function);
+    #[wasm_bindgen(structural, method, js_name = removeEventListener)]
+    fn remove_event_listener (this: &Window, event: &str, f: &Function);
+
+    #[wasm_bindgen(structural, method, getter)]
+    fn pathname (this: &JsLocation) -> String;
+    #[wasm_bindgen(structural, method, getter)]
+    fn search (this: &JsLocation) -> String;
+    #[wasm_bindgen(structural, method, getter)]
+    fn hash (this: &JsLocation) -> String;
+
+    #[wasm_bindgen(structural, method, catch, js_name = pushState)]
+    fn push_state (this: &JsHistory, state: &JsValue, title: &str, url: &str) -> Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = replaceState)]
+    fn replace_state (this: &JsHistory, state: &JsValue, title: &str, url: &str) -> Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch)]
+    fn go (this: &JsHistory, delta: i32) -> Result<(), JsValue>;
+}
+
+/// A listener of the window's events, which is removed once replaced or dropped
+#[cfg(target_family = "wasm")]
+#[derive(Default)]
+struct WindowListener {
+    #[allow(clippy::type_complexity)]
+    inner: UnsafeCell<Option<(&'static str, Closure<dyn FnMut()>)>>,
+}
+
+#[cfg(target_family = "wasm")]
+impl WindowListener {
+    fn set (&self, event: &'static str, f: Box<dyn FnMut()>) {
+        let f = Closure::wrap(f);
+        window().add_event_listener(event, f.as_ref().unchecked_ref());
+        if let Some((event, prev)) = unsafe { &mut *self.inner.get() }.replace((event, f)) {
+            window().remove_event_listener(event, prev.as_ref().unchecked_ref());
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl Drop for WindowListener {
+    #[inline]
+    fn drop (&mut self) {
+        if let Some((event, f)) = self.inner.get_mut().take() {
+            window().remove_event_listener(event, f.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/* BROWSER */
+
+/// The browser's session history, whose locations are the path, query and fragment of the page's URL.
+///
+/// Navigation goes through the History API, and changes made by the user (e.g. going back) are picked up from
+/// `popstate` events. The server must answer every routed path with the application.
+#[cfg(target_family = "wasm")]
+#[derive(Default)]
+pub struct BrowserHistory {
+    listener: WindowListener,
+}
+
+#[cfg(target_family = "wasm")]
+impl BrowserHistory {
+    #[inline]
+    pub fn new () -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl History for BrowserHistory {
+    #[inline]
+    fn location (&self) -> Location {
+        let location = window().location();
+        Location::parse(&format!("{}{}{}", location.pathname(), location.search(), location.hash()))
+    }
+
+    #[inline]
+    fn push (&self, location: &Location) -> Result<(), JsValue> {
+        window().history().push_state(&JsValue::NULL, "", &location.to_string())
+    }
+
+    #[inline]
+    fn replace (&self, location: &Location) -> Result<(), JsValue> {
+        window().history().replace_state(&JsValue::NULL, "", &location.to_string())
+    }
+
+    #[inline]
+    fn go (&self, delta: isize) -> Result<(), JsValue> {
+        window().history().go(delta as i32)
+    }
+
+    #[inline]
+    fn listen (&self, f: Box<dyn FnMut()>) {
+        self.listener.set("popstate", f)
+    }
+}
+
+/* HASH */
+
+/// The browser's session history, with locations stored in the fragment of the page's URL (e.g.
+/// `/index.html#/users/42?tab=posts`).
+///
+/// Unlike [`BrowserHistory`], it works on static file hosts that only serve the application from its own path.
+/// Changes made by the user (e.g. going back, or editing the URL) are picked up from `hashchange` events.
+#[cfg(target_family = "wasm")]
+#[derive(Default)]
+pub struct HashHistory {
+    listener: WindowListener,
+}
+
+#[cfg(target_family = "wasm")]
+impl HashHistory {
+    #[inline]
+    pub fn new () -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl History for HashHistory {
+    #[inline]
+    fn location (&self) -> Location {
+        let hash = window().location().hash();
+        Location::parse(hash.strip_prefix('#').unwrap_or(&hash))
+    }
+
+    #[inline]
+    fn push (&self, location: &Location) -> Result<(), JsValue> {
+        window().history().push_state(&JsValue::NULL, "", &self.href(location))
+    }
+
+    #[inline]
+    fn replace (&self, location: &Location) -> Result<(), JsValue> {
+        window().history().replace_state(&JsValue::NULL, "", &self.href(location))
+    }
+
+    #[inline]
+    fn go (&self, delta: isize) -> Result<(), JsValue> {
+        window().history().go(delta as i32)
+    }
+
+    #[inline]
+    fn listen (&self, f: Box<dyn FnMut()>) {
+        self.listener.set("hashchange", f)
+    }
+
+    #[inline]
+    fn href (&self, location: &Location) -> String {
+        format!("#{location}")
+    }
+}
+
+/* MEMORY */
+
+/// A history that keeps its entries in memory, without touching the page's URL.
+///
+/// It works outside of the browser, which makes it useful to test routes and navigation, and to render routes
+/// on the server. Clones share the same entries.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::router::{History, MemoryHistory};
+///
+/// let history = MemoryHistory::new("/");
+/// history.push(&"/users/42".into()).unwrap();
+/// history.go(-1).unwrap();
+/// assert_eq!(history.location().path, "/");
+/// assert_eq!(history.len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct MemoryHistory {
+    inner: Rc<MemoryInner>,
+}
+
+struct MemoryInner {
+    entries: UnsafeCell<Vec<Location>>,
+    index: Cell<usize>,
+    listener: UnsafeCell<Option<Box<dyn FnMut()>>>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl MemoryHistory {
+    /// Creates a history whose only entry is `url`.
+    #[inline]
+    pub fn new (url: &str) -> Self {
+        return Self {
+            inner: Rc::new(MemoryInner {
+                entries: UnsafeCell::new(vec![Location::parse(url)]),
+                index: Cell::new(0),
+                listener: UnsafeCell::new(None),
+            }),
+        }
+    }
+
+    /// Returns the number of entries in the history, including the ones that can be gone forward to.
+    #[inline]
+    pub fn len (&self) -> usize {
+        unsafe { &*self.inner.entries.get() }.len()
+    }
+
+    /// Returns the position of the current entry.
+    #[inline]
+    pub fn index (&self) -> usize {
+        self.inner.index.get()
+    }
+}
+
+impl History for MemoryHistory {
+    #[inline]
+    fn location (&self) -> Location {
+        let entries = unsafe { &*self.inner.entries.get() };
+        entries[self.index()].clone()
+    }
+
+    #[inline]
+    fn push (&self, location: &Location) -> Result<(), JsValue> {
+        let entries = unsafe { &mut *self.inner.entries.get() };
+        entries.truncate(self.index() + 1);
+        entries.push(location.clone());
+        self.inner.index.set(entries.len() - 1);
+        return Ok(())
+    }
+
+    #[inline]
+    fn replace (&self, location: &Location) -> Result<(), JsValue> {
+        let entries = unsafe { &mut *self.inner.entries.get() };
+        entries[self.index()] = location.clone();
+        return Ok(())
+    }
+
+    /// Moves through the entries, doing nothing if there's no entry `delta` entries away.
+    fn go (&self, delta: isize) -> Result<(), JsValue> {
+        let index = match self.index().checked_add_signed(delta) {
+            Some(x) if delta != 0 && x < self.len() => x,
+            _ => return Ok(()),
+        };
+        self.inner.index.set(index);
+
+        // The listener is taken out while it runs, since it may navigate again
+        if let Some(mut f) = unsafe { &mut *self.inner.listener.get() }.take() {
+            f();
+            unsafe { &mut *self.inner.listener.get() }.get_or_insert(f);
+        }
+        return Ok(())
+    }
+
+    #[inline]
+    fn listen (&self, f: Box<dyn FnMut()>) {
+        unsafe { *self.inner.listener.get() = Some(f) }
+    }
+}
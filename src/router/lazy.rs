@@ -0,0 +1,87 @@
+use super::{Params, Route, RouteView, ViewFn};
+use crate::{dom::{Element, Renderer}, state::Writeable};
+use futures::Future;
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+/// The view of a lazy route, which is loaded the first time the route matches
+pub(super) struct Lazy<R: Renderer> {
+    view: Rc<Writeable<Option<ViewFn<R>>>>,
+    fallback: Option<ViewFn<R>>,
+    load: Cell<Option<Box<dyn FnOnce()>>>,
+}
+
+impl<R: Renderer> Lazy<R> {
+    fn new<L, Fut, F> (load: L, fallback: Option<ViewFn<R>>) -> Self
+    where
+        L: 'static + FnOnce() -> Fut,
+        Fut: 'static + Future<Output = F>,
+        F: 'static + Fn(&Params) -> Result<Element<R>, JsValue>,
+    {
+        let view = Rc::new(Writeable::new(None));
+        let target = Rc::downgrade(&view);
+        let load = move || crate::spawn_local(async move {
+            let f = load().await;
+            if let Some(view) = target.upgrade() {
+                view.set(Some(Rc::new(f) as ViewFn<R>));
+            }
+        });
+
+        return Self {
+            view,
+            fallback,
+            load: Cell::new(Some(Box::new(load))),
+        }
+    }
+
+    /// Returns `true` if the view is loaded.
+    #[inline]
+    pub(super) fn is_loaded (&self) -> bool {
+        self.view.with(Option::is_some)
+    }
+
+    /// Returns the loaded view, or the fallback while it loads, starting to load it if it hasn't yet.
+    pub(super) fn view (&self) -> Option<ViewFn<R>> {
+        if let Some(load) = self.load.take() {
+            load()
+        }
+        return self.view.with(Clone::clone).or_else(|| self.fallback.clone())
+    }
+}
+
+impl<R: Renderer> Route<R> {
+    /// Creates a route whose view is loaded by `load` in the background the first time its pattern matches (e.g.
+    /// a view living in a separately loaded module), rendering nothing until then.
+    ///
+    /// Once loaded, the view is kept by the route and rendered right away whenever it matches again.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spiderweb::{client, router::{Params, Route}};
+    ///
+    /// let route = Route::lazy("/settings", || async {
+    ///     |_: &Params| client! { <h1>{"Settings"}</h1> }
+    /// });
+    /// ```
+    #[inline]
+    pub fn lazy<L, Fut, F> (pattern: &str, load: L) -> Self
+    where
+        L: 'static + FnOnce() -> Fut,
+        Fut: 'static + Future<Output = F>,
+        F: 'static + Fn(&Params) -> Result<Element<R>, JsValue>,
+    {
+        Self::with_view(pattern, RouteView::Lazy(Lazy::new(load, None)))
+    }
+
+    /// Creates a lazy route like [`lazy`](Route::lazy), which renders `fallback` while its view is loading.
+    #[inline]
+    pub fn lazy_with_fallback<L, Fut, F, G> (pattern: &str, load: L, fallback: G) -> Self
+    where
+        L: 'static + FnOnce() -> Fut,
+        Fut: 'static + Future<Output = F>,
+        F: 'static + Fn(&Params) -> Result<Element<R>, JsValue>,
+        G: 'static + Fn(&Params) -> Result<Element<R>, JsValue>,
+    {
+        Self::with_view(pattern, RouteView::Lazy(Lazy::new(load, Some(Rc::new(fallback)))))
+    }
+}
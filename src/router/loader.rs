@@ -0,0 +1,174 @@
+use super::{Params, Route};
+use crate::{dom::{Element, Renderer}, state::{Readable, Writeable}};
+use futures::{future::{abortable, AbortHandle}, Future};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsValue;
+#[cfg(target_family = "wasm")]
+use crate::sync::abort::{AbortController, AbortSignal};
+
+/// State of the data loaded by a route.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LoadState<T, E = JsValue> {
+    /// The loader hasn't resolved yet
+    Loading,
+    /// The loader resolved successfully
+    Loaded (T),
+    /// The loader failed
+    Failed (E),
+}
+
+impl<T, E> LoadState<T, E> {
+    /// Returns `true` if the loader hasn't resolved yet.
+    #[inline]
+    pub fn is_loading (&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+
+    /// Returns the loaded data, if the loader resolved successfully.
+    #[inline]
+    pub fn value (&self) -> Option<&T> {
+        match self {
+            Self::Loaded(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the error of the loader, if it failed.
+    #[inline]
+    pub fn error (&self) -> Option<&E> {
+        match self {
+            Self::Failed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The parameters of a route being loaded, alongside the signal that tells its loader to stop.
+///
+/// Loads are aborted when the route's view is dropped (e.g. the user navigates away, or to the same route with
+/// other parameters) before they resolve. The loader's future is dropped when it's aborted, and pending requests
+/// can be cancelled by handing them the [`signal`](LoadContext::signal) (e.g. through `RequestInit`).
+#[derive(Clone)]
+pub struct LoadContext {
+    params: Params,
+    abort: Rc<Abort>,
+}
+
+struct Abort {
+    aborted: Cell<bool>,
+    #[cfg(target_family = "wasm")]
+    controller: AbortController,
+}
+
+impl LoadContext {
+    #[inline]
+    fn new (params: Params) -> Self {
+        return Self {
+            params,
+            abort: Rc::new(Abort {
+                aborted: Cell::new(false),
+                #[cfg(target_family = "wasm")]
+                controller: AbortController::new(),
+            }),
+        }
+    }
+
+    /// Returns the parameters captured by the matched routes.
+    #[inline]
+    pub fn params (&self) -> &Params {
+        &self.params
+    }
+
+    /// Returns the signal that's aborted alongside the load.
+    #[cfg(target_family = "wasm")]
+    #[inline]
+    pub fn signal (&self) -> AbortSignal {
+        self.abort.controller.signal()
+    }
+
+    /// Returns `true` if the load was aborted.
+    #[inline]
+    pub fn is_aborted (&self) -> bool {
+        self.abort.aborted.get()
+    }
+
+    #[inline]
+    fn abort (&self) {
+        if !self.abort.aborted.replace(true) {
+            #[cfg(target_family = "wasm")]
+            self.abort.controller.abort();
+        }
+    }
+}
+
+/// A load in progress, which is aborted when dropped
+struct Load {
+    handle: AbortHandle,
+    cx: LoadContext,
+}
+
+impl Drop for Load {
+    #[inline]
+    fn drop (&mut self) {
+        self.handle.abort();
+        self.cx.abort();
+    }
+}
+
+impl<R: Renderer> Route<R> {
+    /// Creates a route whose data is loaded by `loader` in the background whenever its pattern matches, while
+    /// `view` renders it as it loads.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spiderweb::{client, dom::Switch, router::{LoadState, Route}};
+    ///
+    /// async fn fetch_user (id: u32) -> Result<String, String> {
+    ///     Ok(format!("User {id}"))
+    /// }
+    ///
+    /// let route = Route::with_loader(
+    ///     "/users/:id",
+    ///     |cx| fetch_user(cx.params().get("id").unwrap_or_default()),
+    ///     |_, user| client! {
+    ///         <section>
+    ///             {Switch::new(user, |x| x.is_loading(), |x| match x {
+    ///                 LoadState::Loading => client! { <p>{"Loading..."}</p> },
+    ///                 LoadState::Loaded(name) => client! { <h1>{name.clone()}</h1> },
+    ///                 LoadState::Failed(e) => client! { <p>{e.clone()}</p> },
+    ///             }.ok())}
+    ///         </section>
+    ///     },
+    /// );
+    /// ```
+    pub fn with_loader<T, E, L, Fut, F> (pattern: &str, loader: L, view: F) -> Self
+    where
+        T: 'static,
+        E: 'static,
+        L: 'static + Fn(LoadContext) -> Fut,
+        Fut: 'static + Future<Output = Result<T, E>>,
+        F: 'static + Fn(&Params, &Readable<LoadState<T, E>>) -> Result<Element<R>, JsValue>,
+    {
+        Self::new(pattern, move |params| {
+            let state = Rc::new(Writeable::new(LoadState::Loading));
+            let cx = LoadContext::new(params.clone());
+            let (fut, handle) = abortable(loader(cx.clone()));
+
+            let target = Rc::downgrade(&state);
+            crate::spawn_local(async move {
+                if let (Ok(result), Some(state)) = (fut.await, target.upgrade()) {
+                    state.set(match result {
+                        Ok(x) => LoadState::Loaded(x),
+                        Err(e) => LoadState::Failed(e),
+                    });
+                }
+            });
+
+            // The load is aborted if the view fails to render, or once it's dropped
+            let load = Load { handle, cx };
+            let element = view(params, &state)?;
+            element.on_cleanup(move || drop((load, state)));
+            return Ok(element)
+        })
+    }
+}
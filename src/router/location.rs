@@ -0,0 +1,131 @@
+use std::fmt::Display;
+
+/// A location inside of the application, made of a path, a query and a fragment (e.g. `/search?q=rust#results`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// Path of the location, which always starts with a `/`
+    pub path: String,
+    /// Query of the location, without its leading `?`
+    pub query: String,
+    /// Fragment of the location, without its leading `#`
+    pub hash: String,
+}
+
+impl Location {
+    /// Parses a location out of the path, query and fragment of a URL.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spiderweb::router::Location;
+    ///
+    /// let location = Location::parse("/search?q=rust#results");
+    /// assert_eq!(location.path, "/search");
+    /// assert_eq!(location.query_param("q").as_deref(), Some("rust"));
+    /// assert_eq!(location.hash, "results");
+    /// ```
+    pub fn parse (url: &str) -> Self {
+        let (rest, hash) = url.split_once('#').unwrap_or((url, ""));
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let path = match path.starts_with('/') {
+            true => path.to_owned(),
+            false => format!("/{path}"),
+        };
+
+        return Self {
+            path,
+            query: query.to_owned(),
+            hash: hash.to_owned(),
+        }
+    }
+
+    /// Resolves `url` relative to this location.
+    ///
+    /// Absolute paths replace the whole location, relative paths replace the last segment of the path, and
+    /// URLs that only contain a query or a fragment keep the current path.
+    pub fn join (&self, url: &str) -> Self {
+        if url.starts_with('/') {
+            return Self::parse(url)
+        } else if let Some(hash) = url.strip_prefix('#') {
+            return Self { hash: hash.to_owned(), ..self.clone() }
+        } else if url.starts_with('?') {
+            return Self::parse(&format!("{}{url}", self.path))
+        }
+
+        let dir = &self.path[..=self.path.rfind('/').unwrap_or(0)];
+        let mut segments = Vec::new();
+        for segment in dir.split('/').chain(url.split('/')) {
+            match segment {
+                "" | "." => {}
+                ".." => { segments.pop(); }
+                x => segments.push(x),
+            }
+        }
+
+        let trailing = match url.ends_with('/') && !segments.is_empty() {
+            true => "/",
+            false => "",
+        };
+        return Self::parse(&format!("/{}{trailing}", segments.join("/")))
+    }
+
+    /// Returns the value of the first query parameter named `name`, if any.
+    ///
+    /// Parameters are decoded, turning `+` into spaces and percent-encoded bytes into their characters.
+    pub fn query_param (&self, name: &str) -> Option<String> {
+        self.query
+            .split('&')
+            .filter_map(|x| match x.split_once('=') {
+                Some((key, value)) => Some((key, value)),
+                None if !x.is_empty() => Some((x, "")),
+                None => None,
+            })
+            .find(|(key, _)| decode(key, true) == name)
+            .map(|(_, value)| decode(value, true))
+    }
+}
+
+impl Display for Location {
+    #[inline]
+    fn fmt (&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)?;
+        if !self.query.is_empty() {
+            write!(f, "?{}", self.query)?;
+        }
+        if !self.hash.is_empty() {
+            write!(f, "#{}", self.hash)?;
+        }
+        return Ok(())
+    }
+}
+
+impl From<&str> for Location {
+    #[inline]
+    fn from (value: &str) -> Self {
+        Self::parse(value)
+    }
+}
+
+/// Decodes a percent-encoded component of a URL, turning `+` into spaces inside of queries
+pub(super) fn decode (s: &str, query: bool) -> String {
+    let input = s.as_bytes();
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        match input[i] {
+            b'+' if query => bytes.push(b' '),
+            b'%' => match s.get(i + 1..i + 3).and_then(|x| u8::from_str_radix(x, 16).ok()) {
+                Some(x) => {
+                    bytes.push(x);
+                    i += 2;
+                }
+                None => bytes.push(b'%'),
+            },
+            b => bytes.push(b),
+        }
+        i += 1;
+    }
+
+    return String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -0,0 +1,380 @@
+use crate::{
+    dom::{
+        element::Content, event::{Click, MouseEvent}, provide_context, render, render_branch, use_context,
+        Children, Component, DefaultRenderer, Element, Renderer,
+    },
+    state::{computed, Readable, Writeable},
+};
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+flat_mod! { location, pattern, history, loader }
+mod lazy;
+
+use lazy::Lazy;
+
+/// A route, which renders its view whenever its pattern matches the current location.
+///
+/// Routes can have children, whose patterns are matched against the rest of the path, and whose views are
+/// rendered into the [`Outlet`] of their parent's view.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::{client, router::{Outlet, Route}};
+///
+/// let routes = [
+///     Route::new("/", |_| client! { <h1>{"Home"}</h1> }),
+///     Route::new("/users/:id", |_| client! { <section>{Outlet}</section> })
+///         .with_child(Route::new("", |params| client! { <p>{format!("User {}", params.get_str("id").unwrap())}</p> }))
+///         .with_child(Route::new("settings", |_| client! { <p>{"Settings"}</p> })),
+/// ];
+/// ```
+pub struct Route<R: Renderer = DefaultRenderer> {
+    pattern: Pattern,
+    view: RouteView<R>,
+    children: Vec<Rc<Route<R>>>,
+}
+
+/// Renders the view of a route with the parameters captured by the matched routes
+type ViewFn<R> = Rc<dyn Fn(&Params) -> Result<Element<R>, JsValue>>;
+
+/// The view of a route, which may still be loading
+enum RouteView<R: Renderer> {
+    Ready (ViewFn<R>),
+    Lazy (Lazy<R>),
+}
+
+impl<R: Renderer> Route<R> {
+    /// Creates a route rendering `view`, with the parameters captured by the matched routes, whenever
+    /// `pattern` matches (e.g. `/users/:id`).
+    #[inline]
+    pub fn new<F: 'static + Fn(&Params) -> Result<Element<R>, JsValue>> (pattern: &str, view: F) -> Self {
+        Self::with_view(pattern, RouteView::Ready(Rc::new(view)))
+    }
+
+    #[inline]
+    fn with_view (pattern: &str, view: RouteView<R>) -> Self {
+        return Self {
+            pattern: Pattern::new(pattern),
+            view,
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child route, whose pattern is relative to this one.
+    #[inline]
+    pub fn with_child (mut self, child: Route<R>) -> Self {
+        self.children.push(Rc::new(child));
+        self
+    }
+
+    /// Returns the view to render (or `None` if there's nothing to render yet), loading lazy views on first use.
+    #[inline]
+    fn view (&self) -> Option<ViewFn<R>> {
+        match self.view {
+            RouteView::Ready(ref f) => Some(f.clone()),
+            RouteView::Lazy(ref lazy) => lazy.view(),
+        }
+    }
+
+    /// Returns `true` if the route's view is loaded, tracking the views of lazy routes as they load.
+    #[inline]
+    fn is_loaded (&self) -> bool {
+        match self.view {
+            RouteView::Ready(_) => true,
+            RouteView::Lazy(ref lazy) => lazy.is_loaded(),
+        }
+    }
+}
+
+/// A route matched by the current location, alongside the parameters captured up to it
+struct Matched<R: Renderer> {
+    route: Rc<Route<R>>,
+    params: Params,
+}
+
+/// Renders the routes matching the location of a [`History`], and navigates through it.
+///
+/// The history is usually a `BrowserHistory`, a `HashHistory` for applications served from static file hosts,
+/// or a [`MemoryHistory`] outside of the browser (e.g. in tests).
+///
+/// Placing a router inside of [`client!`](crate::client) renders the view of the top-level route that matches the
+/// current location, and the views of nested routes are rendered by [`Outlet`]s. Views can retrieve the router
+/// with [`use_router`].
+pub struct Router<R: Renderer = DefaultRenderer> {
+    inner: Rc<RouterInner<R>>,
+}
+
+struct RouterInner<R: Renderer> {
+    history: Box<dyn History>,
+    location: Rc<Writeable<Location>>,
+    matches: Rc<Readable<Vec<Matched<R>>>>,
+}
+
+impl<R: Renderer> Router<R> {
+    /// Creates a router on top of the browser's session history.
+    #[cfg(target_family = "wasm")]
+    #[inline]
+    pub fn new (routes: impl IntoIterator<Item = Route<R>>) -> Self {
+        Self::with_history(BrowserHistory::new(), routes)
+    }
+
+    /// Creates a router on top of the fragment of the page's URL.
+    #[cfg(target_family = "wasm")]
+    #[inline]
+    pub fn with_hash (routes: impl IntoIterator<Item = Route<R>>) -> Self {
+        Self::with_history(HashHistory::new(), routes)
+    }
+
+    /// Creates a router on top of `history`.
+    pub fn with_history (history: impl History, routes: impl IntoIterator<Item = Route<R>>) -> Self {
+        let routes = routes.into_iter().map(Rc::new).collect::<Vec<_>>();
+        let location = Rc::new(Writeable::new(history.location()));
+        let matches = location.map(move |x| {
+            match_routes(&routes, &split_path(&x.path), &Params::default()).unwrap_or_default()
+        });
+
+        let inner = Rc::new(RouterInner {
+            history: Box::new(history),
+            location,
+            matches,
+        });
+
+        let weak = Rc::downgrade(&inner);
+        inner.history.listen(Box::new(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.location.set(inner.history.location());
+            }
+        }));
+
+        return Self { inner }
+    }
+
+    /// Returns the current location.
+    #[inline]
+    pub fn location (&self) -> &Readable<Location> {
+        &self.inner.location
+    }
+
+    /// Returns the parameters captured by the routes matching the current location.
+    #[inline]
+    pub fn params (&self) -> Params {
+        self.inner.matches.with(|x| x.last().map(|x| x.params.clone()).unwrap_or_default())
+    }
+
+    /// Navigates to `url`, resolved relative to the current location, adding it to the history.
+    #[inline]
+    pub fn navigate (&self, url: &str) -> Result<(), JsValue> {
+        let location = self.inner.location.with(|x| x.join(url));
+        self.inner.history.push(&location)?;
+        self.inner.location.set(location);
+        return Ok(())
+    }
+
+    /// Navigates to `url`, resolved relative to the current location, replacing the current entry of the history.
+    #[inline]
+    pub fn replace (&self, url: &str) -> Result<(), JsValue> {
+        let location = self.inner.location.with(|x| x.join(url));
+        self.inner.history.replace(&location)?;
+        self.inner.location.set(location);
+        return Ok(())
+    }
+
+    /// Goes back to the previous location of the history, if any.
+    #[inline]
+    pub fn back (&self) -> Result<(), JsValue> {
+        self.inner.history.go(-1)
+    }
+
+    /// Goes forward to the next location of the history, if any.
+    #[inline]
+    pub fn forward (&self) -> Result<(), JsValue> {
+        self.inner.history.go(1)
+    }
+
+    /// Returns the `href` of links to `url`, resolved relative to the current location.
+    #[inline]
+    pub fn href (&self, url: &str) -> String {
+        self.inner.location.with(|x| self.inner.history.href(&x.join(url)))
+    }
+
+    /// Renders the views of the routes matched at `depth` into `parent`.
+    fn bind_to (&self, parent: &Element<R>, depth: usize) -> Result<(), JsValue> {
+        let router = self.clone();
+        let matches = self.inner.matches.clone();
+
+        // Lazy routes are rendered again once their view is loaded
+        let matched = computed(move || matches.with(|x| x.get(depth).map(|x| (x.clone(), x.route.is_loaded()))));
+        parent.bind_switch(&matched, Option::clone, move |x| {
+            let (matched, _) = x.as_ref()?;
+            let view = matched.route.view()?;
+            render_branch(render::<View, R>((router.clone(), matched.clone(), view, depth), Children::none()))
+        })?;
+
+        parent.on_cleanup(move || drop(matched));
+        return Ok(())
+    }
+}
+
+impl<R: Renderer> Clone for Router<R> {
+    #[inline]
+    fn clone (&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<R: Renderer> Content<R> for &Router<R> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        self.bind_to(parent, 0)
+    }
+}
+
+impl<R: Renderer> Content<R> for Router<R> {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        self.bind_to(parent, 0)
+    }
+}
+
+/// Returns the router rendering the current route, or the one provided with
+/// [`provide_context`](crate::dom::provide_context), if any.
+#[inline]
+pub fn use_router<R: Renderer> () -> Option<Router<R>> {
+    use_context::<Router<R>>().map(|x| Router::clone(&x))
+}
+
+/* OUTLETS */
+
+/// Renders the view of the child route matched by the current location, when placed inside of a route's view.
+pub struct Outlet;
+
+/// Depth of the routes rendered by the outlets of the current view
+#[derive(Clone, Copy)]
+struct Depth (usize);
+
+/// Renders the view of a matched route, providing the router and the depth of its outlets
+struct View;
+
+impl<R: Renderer> Component<R> for View {
+    type Props = (Router<R>, Matched<R>, ViewFn<R>, usize);
+
+    #[inline]
+    fn render ((router, matched, view, depth): Self::Props, _: Children<'_, R>) -> Result<Element<R>, JsValue> {
+        provide_context(router);
+        provide_context(Depth(depth + 1));
+        view(&matched.params)
+    }
+}
+
+impl<R: Renderer> Element<R> {
+    /// Renders the view of the child route matched by the current location into this element.
+    ///
+    /// Fails if the element isn't being rendered inside of a route's view.
+    pub fn bind_outlet (&self) -> Result<(), JsValue> {
+        let router = use_router::<R>().ok_or_else(|| crate::error_value("Outlets must be rendered inside of a route"))?;
+        let depth = use_context::<Depth>().map_or(0, |x| x.0);
+        router.bind_to(self, depth)
+    }
+}
+
+impl<R: Renderer> Content<R> for Outlet {
+    #[inline]
+    fn append_to (self, parent: &Element<R>) -> Result<(), JsValue> {
+        parent.bind_outlet()
+    }
+}
+
+/* LINKS */
+
+/// Props of [`Link`]
+pub struct LinkProps {
+    /// URL navigated to, resolved relative to the current location
+    pub to: String,
+}
+
+/// An anchor that navigates through the router when clicked, instead of loading a new page.
+///
+/// Clicks made with another button than the main one, or while holding a modifier key (e.g. to open the link in
+/// a new tab) are left to the browser. Links outside of the router's views need the router to be provided with
+/// [`provide_context`](crate::dom::provide_context).
+///
+/// # Example
+/// ```rust
+/// use spiderweb::{client, router::Link};
+///
+/// let nav = client! {
+///     <nav>
+///         <Link to={String::from("/")}>{"Home"}</Link>
+///         <Link to={String::from("/about")}>{"About"}</Link>
+///     </nav>
+/// };
+/// ```
+pub struct Link;
+
+impl<R: Renderer> Component<R> for Link {
+    type Props = LinkProps;
+
+    fn render (props: Self::Props, children: Children<'_, R>) -> Result<Element<R>, JsValue> {
+        let element = Element::<R>::create("a");
+        let router = use_router::<R>();
+
+        let href = match router {
+            Some(ref router) => router.href(&props.to),
+            None => props.to.clone(),
+        };
+        element.set_attribute("href", &href)?;
+        children.append_to(&element)?;
+
+        if let Some(router) = router {
+            let to = props.to;
+            element.add_event_listener::<Click>(move |ev: MouseEvent| {
+                if ev.button() != 0 || ev.ctrl_key() || ev.meta_key() || ev.shift_key() || ev.alt_key() {
+                    return
+                }
+
+                ev.prevent_default();
+                if let Err(e) = router.navigate(&to) {
+                    crate::eprintln!(&e)
+                }
+            });
+        }
+
+        return Ok(element)
+    }
+}
+
+/* MATCHING */
+
+/// Matches `segments` against `routes` and their children, returning the chain of matched routes.
+///
+/// Routes are tried in order, and a route with children only matches on its own if the whole path was consumed.
+fn match_routes<R: Renderer> (routes: &[Rc<Route<R>>], segments: &[String], params: &Params) -> Option<Vec<Matched<R>>> {
+    for route in routes {
+        let mut params = params.clone();
+        let Some(len) = route.pattern.match_prefix(segments, &mut params) else { continue };
+        let rest = &segments[len..];
+
+        if let Some(mut chain) = match_routes(&route.children, rest, &params) {
+            chain.insert(0, Matched { route: route.clone(), params });
+            return Some(chain)
+        } else if rest.is_empty() {
+            return Some(vec![Matched { route: route.clone(), params }])
+        }
+    }
+    return None
+}
+
+impl<R: Renderer> Clone for Matched<R> {
+    #[inline]
+    fn clone (&self) -> Self {
+        Self { route: self.route.clone(), params: self.params.clone() }
+    }
+}
+
+impl<R: Renderer> PartialEq for Matched<R> {
+    #[inline]
+    fn eq (&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.route, &other.route) && self.params == other.params
+    }
+}
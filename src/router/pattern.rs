@@ -0,0 +1,120 @@
+use super::location::decode;
+use std::str::FromStr;
+
+/// A pattern that paths are matched against, made of static segments, parameters (e.g. `:id`) and an optional
+/// trailing wildcard (e.g. `*rest`), which matches the rest of the path.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::router::Pattern;
+///
+/// let pattern = Pattern::new("/users/:id/files/*path");
+/// let params = pattern.matches("/users/42/files/docs/report.pdf").unwrap();
+/// assert_eq!(params.get::<u32>("id"), Some(42));
+/// assert_eq!(params.get_str("path"), Some("docs/report.pdf"));
+///
+/// assert!(pattern.matches("/users/42").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static (String),
+    Param (String),
+    Wildcard (String),
+}
+
+/// Parameters captured by the patterns of the matched routes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Params {
+    values: Vec<(String, String)>,
+}
+
+impl Pattern {
+    /// Parses a pattern. Segments following a wildcard are ignored.
+    pub fn new (pattern: &str) -> Self {
+        let mut segments = Vec::new();
+        for segment in pattern.split('/').filter(|x| !x.is_empty()) {
+            if let Some(name) = segment.strip_prefix(':') {
+                segments.push(Segment::Param(name.to_owned()));
+            } else if let Some(name) = segment.strip_prefix('*') {
+                segments.push(Segment::Wildcard(name.to_owned()));
+                break;
+            } else {
+                segments.push(Segment::Static(decode(segment, false)));
+            }
+        }
+        return Self { segments }
+    }
+
+    /// Matches the whole of `path` against the pattern, returning the captured parameters if it matched.
+    #[inline]
+    pub fn matches (&self, path: &str) -> Option<Params> {
+        let segments = split_path(path);
+        let mut params = Params::default();
+        return match self.match_prefix(&segments, &mut params)? == segments.len() {
+            true => Some(params),
+            false => None,
+        }
+    }
+
+    /// Matches the start of `segments` against the pattern, adding the captured parameters to `params`.
+    ///
+    /// Returns the number of segments matched.
+    pub(super) fn match_prefix (&self, segments: &[String], params: &mut Params) -> Option<usize> {
+        let mut captured = Vec::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Wildcard(name) => {
+                    captured.push((name.clone(), segments[i.min(segments.len())..].join("/")));
+                    params.values.extend(captured);
+                    return Some(segments.len());
+                }
+                Segment::Static(x) if segments.get(i) == Some(x) => {}
+                Segment::Param(name) => captured.push((name.clone(), segments.get(i)?.clone())),
+                Segment::Static(_) => return None,
+            }
+        }
+
+        params.values.extend(captured);
+        return Some(self.segments.len())
+    }
+}
+
+impl Params {
+    /// Returns the parameter `name`, parsed as a `T` (e.g. an integer).
+    ///
+    /// Returns `None` if there's no such parameter, or if it couldn't be parsed.
+    #[inline]
+    pub fn get<T: FromStr> (&self, name: &str) -> Option<T> {
+        self.get_str(name)?.parse().ok()
+    }
+
+    /// Returns the parameter `name`, if any.
+    #[inline]
+    pub fn get_str (&self, name: &str) -> Option<&str> {
+        self.values.iter().rev().find(|(x, _)| x == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the names and values of every parameter.
+    #[inline]
+    pub fn iter (&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl From<&str> for Pattern {
+    #[inline]
+    fn from (value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Splits a path into its decoded segments, ignoring empty ones
+#[inline]
+pub(super) fn split_path (path: &str) -> Vec<String> {
+    path.split('/').filter(|x| !x.is_empty()).map(|x| decode(x, false)).collect()
+}
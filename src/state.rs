@@ -1,9 +1,17 @@
-use std::{cell::UnsafeCell, ops::*, rc::Rc};
+use slab::Slab;
+use std::{cell::{Cell, UnsafeCell}, collections::VecDeque, ops::*, rc::{Rc, Weak}};
+
+thread_local! {
+    static CONTEXT: UnsafeCell<Vec<Rc<dyn Dependent>>> = UnsafeCell::new(Vec::new());
+    static NEXT_ID: Cell<usize> = Cell::new(0);
+    static BATCH: Batch = Batch::default();
+}
+
+/// Value of [`Inner::borrow`] while the value is being updated
+const WRITING: isize = -1;
 
 pub struct Readable<T: ?Sized> {
-    strong: UnsafeCell<Vec<Box<dyn FnMut(&T)>>>,
-    weak: UnsafeCell<Vec<Box<dyn FnMut(&T) -> bool>>>,
-    inner: UnsafeCell<T>,
+    inner: Rc<Inner<T>>,
 }
 
 #[repr(transparent)]
@@ -11,41 +19,102 @@ pub struct Writeable<T: ?Sized> {
     inner: Readable<T>,
 }
 
+/// Guard of a subscriber to a [`Readable`].
+///
+/// When dropped, the subscriber is removed from the cell. To keep the subscriber alive for as long as the
+/// cell is, use [`forget`](Subscription::forget).
+#[must_use = "dropping a `Subscription` removes its subscriber"]
+pub struct Subscription {
+    inner: Box<dyn Unsubscribe>,
+    key: usize,
+    id: usize,
+}
+
+struct Inner<T: ?Sized> {
+    subscribers: UnsafeCell<Slab<Subscriber<T>>>,
+    /// Length of the longest chain of derived cells leading to this one
+    height: Cell<usize>,
+    /// Whether the subscribers of this cell are waiting to be notified
+    queued: Cell<bool>,
+    /// Number of active readers, or [`WRITING`] if the value is being updated
+    borrow: Cell<isize>,
+    /// Updates made while the value was borrowed, applied once it's released
+    deferred: UnsafeCell<Vec<Box<dyn FnOnce(&mut T)>>>,
+    value: UnsafeCell<T>,
+}
+
+struct Subscriber<T: ?Sized> {
+    id: usize,
+    /// Whether this is a derived cell, which is updated as soon as the value changes
+    dependent: bool,
+    /// Taken while the subscriber is being called
+    f: Option<Box<dyn FnMut(&T) -> bool>>,
+}
+
+/// State of the current batch
+#[derive(Default)]
+struct Batch {
+    depth: Cell<usize>,
+    dirty: UnsafeCell<Vec<Rc<dyn Dependent>>>,
+    effects: UnsafeCell<VecDeque<Box<dyn FnOnce()>>>,
+}
+
 impl<T: ?Sized> Readable<T> {
     #[inline]
-    pub const fn new(t: T) -> Self where T: Sized {
+    pub fn new(t: T) -> Self where T: Sized {
         return Self {
-            strong: UnsafeCell::new(Vec::new()),
-            weak: UnsafeCell::new(Vec::new()),
-            inner: UnsafeCell::new(t),
+            inner: Rc::new(Inner {
+                subscribers: UnsafeCell::new(Slab::new()),
+                height: Cell::new(0),
+                queued: Cell::new(false),
+                borrow: Cell::new(0),
+                deferred: UnsafeCell::new(Vec::new()),
+                value: UnsafeCell::new(t),
+            }),
         };
     }
+}
 
+impl<T: ?Sized + 'static> Readable<T> {
     #[inline]
     pub fn get(&self) -> T
     where
         T: Copy,
     {
-        unsafe { *self.inner.get() }
+        self.track();
+        if self.inner.borrow.get() == WRITING {
+            borrow_panic()
+        }
+        unsafe { *self.inner.value.get() }
     }
 
+    /// Calls `f` with a reference to the current value.
+    ///
+    /// # Panics
+    /// This method panics if it's called while the value is being updated (e.g. from inside of [`update`](Writeable::update)).
     #[inline]
     pub fn with<U, F: FnOnce(&T) -> U>(&self, f: F) -> U {
-        unsafe { f(&*self.inner.get()) }
+        self.track();
+        self.inner.read();
+        let result = f(unsafe { &*self.inner.value.get() });
+        self.inner.release();
+        return result;
     }
 
     #[inline]
     pub fn map<U: 'static, F: 'static + FnMut(&T) -> U>(&self, mut f: F) -> Rc<Readable<U>> {
         let result = Rc::new(Writeable::new(self.with(&mut f)));
+        result.inner.inner.height.set(self.inner.height.get() + 1);
         let target = Rc::downgrade(&result);
 
-        self.subscribe_weak(move |x| match target.upgrade() {
+        self.subscribe_dependent(Box::new(move |x| match target.upgrade() {
             Some(target) => {
                 target.set(f(x));
                 true
             }
             None => false,
-        });
+        }))
+        .forget();
 
         return unsafe { Rc::from_raw(Rc::into_raw(result).cast()) };
     }
@@ -55,50 +124,101 @@ impl<T: ?Sized> Readable<T> {
         &self,
         target: Writeable<U>,
         mut f: F,
-    ) {
+    ) -> Subscription {
         self.subscribe(move |x| target.set(f(x)))
     }
 
     #[inline]
-    pub fn subscribe<F: 'static + FnMut(&T)>(&self, f: F) {
+    pub fn subscribe<F: 'static + FnMut(&T)>(&self, f: F) -> Subscription {
         self.subscribe_boxed(Box::new(f))
     }
 
+    /// Subscribes `f` to the cell, until it returns `false`.
     #[inline]
-    pub fn subscribe_weak<F: 'static + FnMut(&T) -> bool>(&self, f: F) {
+    pub fn subscribe_weak<F: 'static + FnMut(&T) -> bool>(&self, f: F) -> Subscription {
         self.subscribe_weak_boxed(Box::new(f))
     }
 
     #[inline]
-    pub fn subscribe_boxed(&self, f: Box<dyn FnMut(&T)>) {
-        unsafe { &mut *self.strong.get() }.push(f)
+    pub fn subscribe_boxed(&self, mut f: Box<dyn FnMut(&T)>) -> Subscription {
+        self.subscribe_weak_boxed(Box::new(move |x| {
+            f(x);
+            true
+        }))
     }
 
     #[inline]
-    pub fn subscribe_weak_boxed(&self, f: Box<dyn FnMut(&T) -> bool>) {
-        unsafe { &mut *self.weak.get() }.push(f)
+    pub fn subscribe_weak_boxed(&self, f: Box<dyn FnMut(&T) -> bool>) -> Subscription {
+        self.insert_subscriber(f, false)
+    }
+
+    #[inline]
+    fn subscribe_dependent(&self, f: Box<dyn FnMut(&T) -> bool>) -> Subscription {
+        self.insert_subscriber(f, true)
+    }
+
+    fn insert_subscriber(&self, f: Box<dyn FnMut(&T) -> bool>, dependent: bool) -> Subscription {
+        let id = NEXT_ID.with(|x| x.replace(x.get() + 1));
+        let key = unsafe { &mut *self.inner.subscribers.get() }.insert(Subscriber {
+            id,
+            dependent,
+            f: Some(f),
+        });
+
+        return Subscription {
+            inner: Box::new(Rc::downgrade(&self.inner)),
+            key,
+            id,
+        };
+    }
+
+    /// Records this cell as a dependency of the computation currently being evaluated, if any.
+    fn track(&self) {
+        let node = match CONTEXT.with(|ctx| unsafe { &*ctx.get() }.last().cloned()) {
+            Some(node) => node,
+            None => return,
+        };
+
+        let source = Rc::as_ptr(&self.inner) as *const ();
+        let dependencies = node.dependencies();
+        dependencies.next_height.set(usize::max(
+            dependencies.next_height.get(),
+            self.inner.height.get() + 1,
+        ));
+
+        let dependent = node.clone();
+        dependencies.track(source, move || {
+            self.subscribe_dependent(Box::new(move |_| dependent.clone().schedule()))
+        });
     }
 }
 
 impl<T: ?Sized> Writeable<T> {
     #[inline]
-    pub const fn new(t: T) -> Self where T: Sized {
+    pub fn new(t: T) -> Self where T: Sized {
         return Self {
             inner: Readable::new(t),
         };
     }
+}
 
+impl<T: ?Sized + 'static> Writeable<T> {
+    /// Sets the value of the cell.
+    ///
+    /// If the value is currently borrowed (e.g. when called from inside one of the cell's subscribers),
+    /// the update will be applied once it's released.
     #[inline]
     pub fn set(&self, v: T) where T: Sized {
-        unsafe { *self.inner.inner.get() = v };
-        self.notify()
+        self.update_deferred(move |x| *x = v)
     }
 
+    /// Replaces the value of the cell, returning the previous one.
+    ///
+    /// # Panics
+    /// This method panics if the value is currently borrowed (e.g. when called from inside one of the cell's subscribers).
     #[inline]
     pub fn replace(&self, v: T) -> T where T: Sized {
-        let prev = core::mem::replace(unsafe { &mut *self.inner.inner.get() }, v);
-        self.notify();
-        return prev;
+        self.update(move |x| core::mem::replace(x, v))
     }
 
     #[inline]
@@ -109,35 +229,371 @@ impl<T: ?Sized> Writeable<T> {
         self.replace(Default::default())
     }
 
+    /// Updates the value of the cell.
+    ///
+    /// # Panics
+    /// This method panics if the value is currently borrowed (e.g. when called from inside one of the cell's subscribers).
+    /// In that case, use [`update_deferred`](Writeable::update_deferred) instead.
     #[inline]
     pub fn update<U, F: FnOnce(&mut T) -> U>(&self, f: F) -> U {
-        let res = f(unsafe { &mut *self.inner.inner.get() });
-        self.notify();
+        let inner = &self.inner.inner;
+        if inner.borrow.get() != 0 {
+            borrow_panic()
+        }
+
+        inner.borrow.set(WRITING);
+        let res = f(unsafe { &mut *inner.value.get() });
+        inner.release();
+        inner.notify();
         return res;
     }
 
+    /// Updates the value of the cell, or queues the update to be applied once the value is released,
+    /// if it's currently borrowed.
     #[inline]
-    fn notify(&self) {
-        let inner = unsafe { &*self.inner.inner.get() };
-        let strong = unsafe { &mut *self.inner.strong.get() };
-        let weak = unsafe { &mut *self.inner.weak.get() };
+    pub fn update_deferred<F: 'static + FnOnce(&mut T)>(&self, f: F) {
+        let inner = &self.inner.inner;
+        match inner.borrow.get() {
+            0 => self.update(f),
+            _ => unsafe { &mut *inner.deferred.get() }.push(Box::new(f)),
+        }
+    }
+}
+
+/// Executes `f`, delaying the notification of every cell updated inside of it until it has completed.
+///
+/// Derived cells are evaluated in topological order before any subscriber is notified, so each subscriber
+/// runs at most once per batch and only ever sees consistent values.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::state::{Writeable, batch};
+///
+/// let first = Writeable::new(String::from("Ada"));
+/// let last = Writeable::new(String::from("Lovelace"));
+/// first.subscribe(|x| spiderweb::println!("{x}")).forget();
+///
+/// batch(|| {
+///     first.set(String::from("Alan"));
+///     last.set(String::from("Turing"));
+/// });
+/// ```
+pub fn batch<U, F: FnOnce() -> U>(f: F) -> U {
+    let guard = BatchGuard::open();
+    let result = f();
+
+    if guard.outermost {
+        flush();
+    }
+
+    return result;
+}
+
+/// Keeps the current batch open, closing it once dropped (even if the batch panicked)
+struct BatchGuard {
+    outermost: bool,
+}
+
+impl BatchGuard {
+    #[inline]
+    fn open() -> Self {
+        let depth = BATCH.with(|batch| batch.depth.replace(batch.depth.get() + 1));
+        return Self { outermost: depth == 0 };
+    }
+}
+
+impl Drop for BatchGuard {
+    #[inline]
+    fn drop(&mut self) {
+        BATCH.with(|batch| batch.depth.set(batch.depth.get() - 1));
+    }
+}
+
+/// Propagates every pending change, until there's nothing left to update.
+///
+/// Changes left pending by a panic are propagated by the next batch.
+fn flush() {
+    loop {
+        let dirty = BATCH.with(|batch| {
+            let dirty = unsafe { &mut *batch.dirty.get() };
+            let idx = dirty
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, x)| x.dependencies().height.get())
+                .map(|(i, _)| i)?;
+            Some(dirty.swap_remove(idx))
+        });
+
+        if let Some(dirty) = dirty {
+            dirty.recompute();
+            continue;
+        }
 
-        for sub in strong.iter_mut() {
-            sub(inner)
+        match BATCH.with(|batch| unsafe { &mut *batch.effects.get() }.pop_front()) {
+            Some(effects) => effects(),
+            None => break,
         }
+    }
+}
 
-        let mut i = 0;
-        while i < weak.len() {
-            if !unsafe { weak.get_unchecked_mut(i) }(inner) {
-                weak.swap_remove(i);
-            } else {
-                i += 1
+/// Creates a new [`Readable`] whose value is computed by `f`.
+///
+/// Every cell read inside `f` is recorded as a dependency of the result, which will be re-evaluated
+/// whenever any of them changes. The set of dependencies is recorded again on every evaluation, so cells
+/// that are only read conditionally will be tracked only while they are being read.
+///
+/// # Example
+/// ```rust
+/// use spiderweb::state::{Writeable, computed};
+/// use std::rc::Rc;
+///
+/// let a = Rc::new(Writeable::new(1));
+/// let b = Rc::new(Writeable::new(2));
+///
+/// let sum = computed({
+///     let (a, b) = (a.clone(), b.clone());
+///     move || a.get() + b.get()
+/// });
+///
+/// a.set(3);
+/// assert_eq!(sum.get(), 5);
+/// ```
+#[inline]
+pub fn computed<U: 'static, F: 'static + FnMut() -> U>(f: F) -> Rc<Readable<U>> {
+    let node = Rc::new(Computed {
+        dependencies: Dependencies::default(),
+        target: UnsafeCell::new(Weak::new()),
+        f: UnsafeCell::new(f),
+    });
+
+    let result = Rc::new(Writeable::new(node.clone().evaluate()));
+    result.inner.inner.height.set(node.dependencies.height.get());
+    unsafe { *node.target.get() = Rc::downgrade(&result) };
+    return unsafe { Rc::from_raw(Rc::into_raw(result).cast()) };
+}
+
+/// A computation that depends on the cells it reads
+trait Dependent {
+    fn dependencies(&self) -> &Dependencies;
+    /// Marks the computation as dirty, returning `false` if it's no longer needed.
+    fn schedule(self: Rc<Self>) -> bool;
+    fn recompute(self: Rc<Self>);
+}
+
+#[cold]
+#[track_caller]
+fn borrow_panic() -> ! {
+    panic!("cell already borrowed: a cell cannot be read while it's being updated, nor updated while it's being read (use `Writeable::set` or `Writeable::update_deferred` to queue an update instead)")
+}
+
+trait Unsubscribe {
+    fn unsubscribe(&self, key: usize, id: usize);
+}
+
+/// Cells read by a [`Dependent`], alongside the subscription made to each of them.
+#[derive(Default)]
+struct Dependencies {
+    current: UnsafeCell<Vec<(*const (), Subscription)>>,
+    next: UnsafeCell<Vec<(*const (), Subscription)>>,
+    height: Cell<usize>,
+    next_height: Cell<usize>,
+    dirty: Cell<bool>,
+}
+
+struct Computed<U, F> {
+    dependencies: Dependencies,
+    target: UnsafeCell<Weak<Writeable<U>>>,
+    f: UnsafeCell<F>,
+}
+
+impl Dependencies {
+    /// Calls `subscribe` if `source` wasn't subscribed to yet.
+    fn track<F: FnOnce() -> Subscription>(&self, source: *const (), subscribe: F) {
+        let current = unsafe { &mut *self.current.get() };
+        let next = unsafe { &mut *self.next.get() };
+
+        if next.iter().any(|(x, _)| *x == source) {
+            return;
+        }
+
+        if let Some(i) = current.iter().position(|(x, _)| *x == source) {
+            next.push(current.swap_remove(i));
+            return;
+        }
+
+        next.push((source, subscribe()));
+    }
+}
+
+impl<U: 'static, F: 'static + FnMut() -> U> Computed<U, F> {
+    fn evaluate(self: Rc<Self>) -> U {
+        self.dependencies.next_height.set(0);
+        CONTEXT.with(|ctx| unsafe { &mut *ctx.get() }.push(self.clone()));
+        let evaluation = Evaluation;
+        let value = unsafe { (&mut *self.f.get())() };
+        drop(evaluation);
+
+        unsafe {
+            let current = &mut *self.dependencies.current.get();
+            let next = &mut *self.dependencies.next.get();
+            core::mem::swap(current, next);
+        }
+
+        // Unsubscribe from the cells that weren't read this time
+        let _ = core::mem::take(unsafe { &mut *self.dependencies.next.get() });
+
+        self.dependencies.height.set(self.dependencies.next_height.get());
+        return value;
+    }
+}
+
+/// Removes the computation being evaluated from the context once dropped (even if it panicked)
+struct Evaluation;
+
+impl Drop for Evaluation {
+    #[inline]
+    fn drop(&mut self) {
+        CONTEXT.with(|ctx| unsafe { &mut *ctx.get() }.pop());
+    }
+}
+
+impl<U: 'static, F: 'static + FnMut() -> U> Dependent for Computed<U, F> {
+    #[inline]
+    fn dependencies(&self) -> &Dependencies {
+        &self.dependencies
+    }
+
+    fn schedule(self: Rc<Self>) -> bool {
+        if unsafe { &*self.target.get() }.strong_count() == 0 {
+            return false;
+        }
+
+        if !self.dependencies.dirty.replace(true) {
+            BATCH.with(|batch| unsafe { &mut *batch.dirty.get() }.push(self));
+        }
+        return true;
+    }
+
+    fn recompute(self: Rc<Self>) {
+        self.dependencies.dirty.set(false);
+        if let Some(target) = unsafe { &*self.target.get() }.upgrade() {
+            let value = self.clone().evaluate();
+            target.inner.inner.height.set(self.dependencies.height.get());
+            target.set(value);
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Inner<T> {
+    #[inline]
+    fn read(&self) {
+        match self.borrow.get() {
+            WRITING => borrow_panic(),
+            x => self.borrow.set(x + 1),
+        }
+    }
+
+    /// Releases a borrow of the value, applying the deferred updates if it was the last one.
+    fn release(self: &Rc<Self>) {
+        match self.borrow.get() {
+            WRITING | 1 => self.borrow.set(0),
+            x => return self.borrow.set(x - 1),
+        }
+
+        let deferred = core::mem::take(unsafe { &mut *self.deferred.get() });
+        if deferred.is_empty() || std::thread::panicking() {
+            return;
+        }
+
+        self.borrow.set(WRITING);
+        for f in deferred {
+            f(unsafe { &mut *self.value.get() });
+        }
+        self.release();
+        self.notify();
+    }
+
+    /// Updates the derived cells, and queues the subscribers to be notified at the end of the current batch.
+    fn notify(self: &Rc<Self>) {
+        batch(|| {
+            self.call(true);
+
+            if !self.queued.replace(true) {
+                let inner = self.clone();
+                BATCH.with(|batch| unsafe { &mut *batch.effects.get() }.push_back(Box::new(move || inner.run())));
+            }
+        })
+    }
+
+    /// Notifies the subscribers that were queued by [`notify`](Inner::notify).
+    #[inline]
+    fn run(self: &Rc<Self>) {
+        self.queued.set(false);
+        self.call(false);
+    }
+
+    /// Calls the subscribers that are (or aren't) `dependent`, removing the ones that return `false`.
+    ///
+    /// Subscribers added while this method runs won't be called until the next notification, and the value
+    /// stays borrowed until every subscriber has been called.
+    fn call(self: &Rc<Self>, dependent: bool) {
+        self.read();
+        let value = unsafe { &*self.value.get() };
+        let keys = unsafe { &*self.subscribers.get() }
+            .iter()
+            .filter(|(_, sub)| sub.dependent == dependent)
+            .map(|(key, sub)| (key, sub.id))
+            .collect::<Vec<_>>();
+
+        for (key, id) in keys {
+            let mut f = match unsafe { &mut *self.subscribers.get() }.get_mut(key) {
+                Some(sub) if sub.id == id => match sub.f.take() {
+                    Some(f) => f,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let keep = f(value);
+            let subscribers = unsafe { &mut *self.subscribers.get() };
+            match subscribers.get_mut(key) {
+                Some(sub) if sub.id == id && keep => sub.f = Some(f),
+                Some(sub) if sub.id == id => drop(subscribers.remove(key)),
+                _ => {}
             }
         }
+
+        self.release();
+    }
+}
+
+impl<T: ?Sized> Unsubscribe for Weak<Inner<T>> {
+    #[inline]
+    fn unsubscribe(&self, key: usize, id: usize) {
+        let Some(inner) = self.upgrade() else { return };
+        let subscribers = unsafe { &mut *inner.subscribers.get() };
+        if subscribers.get(key).map_or(false, |sub| sub.id == id) {
+            drop(subscribers.remove(key));
+        }
+    }
+}
+
+impl Subscription {
+    /// Consumes the guard without removing the subscriber, which will live for as long as the cell does.
+    #[inline]
+    pub fn forget(self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for Subscription {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.unsubscribe(self.key, self.id)
     }
 }
 
-impl<T> Deref for Writeable<T> {
+impl<T: ?Sized> Deref for Writeable<T> {
     type Target = Readable<T>;
 
     #[inline]
@@ -149,14 +605,14 @@ impl<T> Deref for Writeable<T> {
 macro_rules! impl_assign {
     ($($trait:ident as $f:ident),+) => {
         $(
-            impl<T: $trait<U>, U> $trait<U> for Writeable<T> {
+            impl<T: ?Sized + 'static + $trait<U>, U> $trait<U> for Writeable<T> {
                 #[inline]
                 fn $f (&mut self, rhs: U) {
                     self.update(|x| x.$f(rhs))
                 }
             }
 
-            impl<T> Writeable<T> {
+            impl<T: ?Sized + 'static> Writeable<T> {
                 #[inline]
                 pub fn $f<U> (&self, rhs: U) where T: $trait<U> {
                     self.update(|x| x.$f(rhs))
@@ -9,8 +9,18 @@ extern "C" {
     pub fn aborted (this: &AbortSignal) -> bool;
     #[wasm_bindgen(structural, method, getter)]
     pub fn reason (this: &AbortSignal) -> JsValue;
-    #[wasm_bindgen(structural, method)]
-    pub fn abort (this: &AbortSignal) -> AbortSignal;
+    #[wasm_bindgen(static_method_of = AbortSignal)]
+    pub fn abort () -> AbortSignal;
+    #[wasm_bindgen(static_method_of = AbortSignal)]
+    pub fn timeout (millis: f64) -> AbortSignal;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub type AbortController;
+
+    #[wasm_bindgen(constructor)]
+    pub fn new () -> AbortController;
     #[wasm_bindgen(structural, method, getter)]
-    pub fn timeout (this: &AbortSignal, millis: f64) -> AbortSignal;
-}
\ No newline at end of file
+    pub fn signal (this: &AbortController) -> AbortSignal;
+    #[wasm_bindgen(structural, method, js_name = abort)]
+    pub fn abort (this: &AbortController);
+}
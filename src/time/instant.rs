@@ -1,10 +1,17 @@
 use std::{time::Duration, ops::{Add, AddAssign, Sub, SubAssign}};
+#[cfg(target_family = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
+#[cfg(target_family = "wasm")]
 thread_local! {
     static PERFORMANCE: Performance = performance();
 }
 
+/// Time origin of the process outside of WebAssembly, like the time origin of the page is in the browser
+#[cfg(not(target_family = "wasm"))]
+static ORIGIN: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+
+#[cfg(target_family = "wasm")]
 #[wasm_bindgen]
 extern {
     type Performance;
@@ -37,7 +44,7 @@ extern {
 ///
 /// Example:
 ///
-/// ```no_run
+/// ```ignore
 /// use spiderweb::time::{Duration, Instant};
 /// use spiderweb::task::sleep;
 ///
@@ -135,7 +142,10 @@ impl Instant {
     #[inline]
     #[must_use]
     pub fn now () -> Self {
-        Self(Duration::from_secs_f64(PERFORMANCE.with(Performance::now) / 1000.))
+        #[cfg(target_family = "wasm")]
+        return Self(Duration::from_secs_f64(PERFORMANCE.with(Performance::now) / 1000.));
+        #[cfg(not(target_family = "wasm"))]
+        return Self(ORIGIN.get_or_init(std::time::Instant::now).elapsed());
     }
 
     /// Returns the amount of time elapsed from another instant to this one,
@@ -151,7 +161,7 @@ impl Instant {
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
     /// use spiderweb::time::{Duration, Instant};
     /// use spiderweb::task::sleep;
     ///
@@ -177,7 +187,7 @@ impl Instant {
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
     /// use spiderweb::time::{Duration, Instant};
     /// use spiderweb::task::sleep;
     ///
@@ -198,7 +208,7 @@ impl Instant {
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
     /// use spiderweb::time::{Duration, Instant};
     /// use spiderweb::task::sleep;
     ///
@@ -226,7 +236,7 @@ impl Instant {
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
     /// use spiderweb::time::{Duration, Instant};
     /// use spiderweb::task::sleep;
     ///
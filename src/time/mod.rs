@@ -1,12 +1,16 @@
-flat_mod! { interval, timeout, instant, system_time }
+flat_mod! { instant }
+#[cfg(target_family = "wasm")]
+flat_mod! { interval, timeout, system_time }
 pub use std::time::Duration;
 
 /// Maximum ammount of time that can be passed to [`Interval`] or [`Timeout`].
 ///
 /// If a grater duration than this one is passed to any of this types, their durations will
 /// saturate to this value.
+#[cfg(target_family = "wasm")]
 pub const MAX_DURATION: Duration = Duration::from_millis(i32::MAX as u64);
 
+#[cfg(target_family = "wasm")]
 #[inline]
 pub(super) fn timeout2millis(dur: Duration) -> i32 {
     match i32::try_from(dur.as_millis()) {
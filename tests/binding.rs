@@ -0,0 +1,128 @@
+#![cfg(not(target_family = "wasm"))]
+
+use spiderweb::{client, dom::{attribute::{AttributeValue, Property}, testing::assert_html, Element}, state::{batch, Readable, Writeable}};
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+#[test]
+fn bind_value() -> Result<(), JsValue> {
+    let name = Rc::new(Writeable::new(String::from("Alex")));
+    let bio = Rc::new(Writeable::new(String::new()));
+
+    let form = client! {
+        <form>
+            <input bind:value={name} />
+            <textarea bind:value={&bio}></textarea>
+        </form>
+    }?;
+
+    let input = form.html_node().query_selector("input")?.unwrap();
+    assert_eq!(input.get_property("value"), Some(Property::String(String::from("Alex"))));
+
+    input.input("Sam");
+    assert_eq!(name.with(Clone::clone), "Sam");
+
+    name.set(String::from("Kim"));
+    assert_eq!(input.get_property("value"), Some(Property::String(String::from("Kim"))));
+
+    form.html_node().query_selector("textarea")?.unwrap().input("Hello");
+    assert_eq!(bio.with(Clone::clone), "Hello");
+    assert_eq!(name.with(Clone::clone), "Kim");
+
+    return Ok(());
+}
+
+#[test]
+fn bind_checked() -> Result<(), JsValue> {
+    let agreed = Rc::new(Writeable::new(false));
+
+    let checkbox = Element::new("input");
+    checkbox.set_attribute("type", "checkbox")?;
+    checkbox.bind_checked(&agreed)?;
+    assert_eq!(checkbox.html_node().get_property("checked"), Some(Property::Bool(false)));
+
+    checkbox.html_node().click();
+    assert!(agreed.get());
+
+    agreed.set(false);
+    assert_eq!(checkbox.html_node().get_property("checked"), Some(Property::Bool(false)));
+
+    // The cell isn't kept alive by the element
+    drop(agreed);
+    checkbox.html_node().click();
+
+    return Ok(());
+}
+
+#[test]
+fn bind_number() -> Result<(), JsValue> {
+    let amount = Rc::new(Writeable::new(2.0f64));
+    let input = client! { <input bind:number={amount} /> }?;
+    let node = input.html_node();
+    assert_eq!(node.get_property("value"), Some(Property::String(String::from("2"))));
+
+    // Updates from the element aren't written back to it
+    node.input("1.50");
+    assert_eq!(amount.get(), 1.5);
+    assert_eq!(node.get_property("value"), Some(Property::String(String::from("1.50"))));
+
+    node.input("1.5e");
+    assert_eq!(amount.get(), 1.5);
+
+    amount.set(4.25);
+    assert_eq!(node.get_property("value"), Some(Property::String(String::from("4.25"))));
+
+    // Values set after the element's own update are still written to it, even within the same batch
+    batch(|| {
+        node.input("3.50");
+        amount.set(3.75);
+    });
+    assert_eq!(node.get_property("value"), Some(Property::String(String::from("3.75"))));
+
+    batch(|| {
+        amount.set(1.0);
+        node.input("2.50");
+    });
+    assert_eq!(amount.get(), 2.5);
+    assert_eq!(node.get_property("value"), Some(Property::String(String::from("2.50"))));
+
+    return Ok(());
+}
+
+#[test]
+fn bind_selected() -> Result<(), JsValue> {
+    let color = Rc::new(Writeable::new(String::from("green")));
+    let select = client! {
+        <select bind:selected={color}>
+            <option value={"red"}>{"Red"}</option>
+            <option value={"green"}>{"Green"}</option>
+        </select>
+    }?;
+
+    let node = select.html_node();
+    assert_eq!(node.get_property("value"), Some(Property::String(String::from("green"))));
+
+    // The selection is only committed on `change`
+    node.input("red");
+    assert_eq!(color.with(Clone::clone), "green");
+    node.change();
+    assert_eq!(color.with(Clone::clone), "red");
+
+    return Ok(());
+}
+
+/// Binds a cell whose value may be unsized, which only needs a reference to it
+fn label<T: ?Sized + 'static + AsRef<str> + AttributeValue> (state: &Readable<T>) -> Result<Element, JsValue> {
+    client! { <label title={state}>{state}</label> }
+}
+
+#[test]
+fn bind_unsized() -> Result<(), JsValue> {
+    let text = Writeable::new(String::from("Name"));
+    let element = label(&text)?;
+    assert_html(&element, r#"<label title="Name">Name</label>"#);
+
+    text.set(String::from("Email"));
+    assert_html(&element, r#"<label title="Email">Email</label>"#);
+    return Ok(());
+}
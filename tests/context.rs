@@ -0,0 +1,24 @@
+#![cfg(not(target_family = "wasm"))]
+
+use spiderweb::{client, component, dom::{provide_context, use_context, Element}};
+use std::panic::catch_unwind;
+use wasm_bindgen::JsValue;
+
+struct Theme {
+    color: &'static str,
+}
+
+#[component]
+fn Faulty() -> Result<Element, JsValue> {
+    provide_context(Theme { color: "red" });
+    panic!("render failed")
+}
+
+#[test]
+fn panic_inside_render() {
+    let result = catch_unwind(|| client! { <div><Faulty /></div> });
+    assert!(result.is_err());
+
+    // The scope of the component was left, so its values aren't visible outside of it
+    assert!(use_context::<Theme>().map(|x| x.color).is_none());
+}
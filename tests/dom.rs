@@ -1,56 +1,494 @@
+#![cfg(target_family = "wasm")]
+
+use futures::StreamExt;
 use spiderweb::{
+    client,
     dom::{
         element::{Element, body},
+        event::{Click, InputEvent},
     },
     state::Writeable,
+    time::{Duration, Interval},
 };
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
 use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
-// #[wasm_bindgen_test]
-// async fn client_macro() -> Result<(), JsValue> {
-//     let text = StateCell::new(String::new());
-//
-//     let mut name = "Alex".chars();
-//     let interval = Interval::new(
-//         || {
-//             if let Some(c) = name.next() {
-//                 text.update(|x| x.push(c))
-//             }
-//         },
-//         Duration::from_millis(500),
-//     );
-//
-//     let text = client! {
-//         <span>
-//             <i>{"Hello, "}</i>
-//             <b>{&text}</b>
-//         </span>
-//     }?;
-//
-//     append_to_body(text)?;
-//     interval.take(5).collect::<()>().await;
-//
-//     return Ok(());
-// }
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, PartialEq)]
+    type HtmlNode;
+
+    #[wasm_bindgen(js_namespace = document, js_name = querySelector)]
+    fn query_selector (selector: &str) -> Option<HtmlNode>;
+    #[wasm_bindgen(js_namespace = document, js_name = querySelectorAll)]
+    fn query_selector_all_inner (selector: &str) -> JsValue;
+    #[wasm_bindgen(method, getter, js_name = textContent)]
+    fn text_content (this: &HtmlNode) -> String;
+    #[wasm_bindgen(method, getter, js_name = parentNode)]
+    fn parent_node (this: &HtmlNode) -> Option<HtmlNode>;
+    #[wasm_bindgen(method, js_name = getAttribute)]
+    fn get_attribute (this: &HtmlNode, name: &str) -> Option<String>;
+    #[wasm_bindgen(method, getter)]
+    fn draggable (this: &HtmlNode) -> bool;
+    #[wasm_bindgen(method)]
+    fn click (this: &HtmlNode);
+    #[wasm_bindgen(method, getter, js_name = outerHTML)]
+    fn outer_html (this: &HtmlNode) -> String;
+}
+
+fn query_selector_all (selector: &str) -> Vec<HtmlNode> {
+    return js_sys::Array::from(&query_selector_all_inner(selector))
+        .iter()
+        .map(JsCast::unchecked_into)
+        .collect()
+}
+
+fn texts (nodes: &[HtmlNode]) -> Vec<String> {
+    return nodes.iter().map(HtmlNode::text_content).collect()
+}
+
+fn outer_htmls (nodes: &[HtmlNode]) -> Vec<String> {
+    return nodes.iter().map(HtmlNode::outer_html).collect()
+}
+
+#[wasm_bindgen_test]
+async fn client_macro() -> Result<(), JsValue> {
+    let text = Writeable::new(String::new());
+
+    let mut name = "Alex".chars();
+    let interval = Interval::new(
+        || {
+            if let Some(c) = name.next() {
+                text.update(|x| x.push(c))
+            }
+        },
+        Duration::from_millis(500),
+    );
+
+    let text = client! {
+        <span>
+            <i>{"Hello, "}</i>
+            <b>{&text}</b>
+        </span>
+    }?;
+
+    body().append_child(text)?;
+    interval.take(5).collect::<()>().await;
+
+    return Ok(());
+}
 
 #[wasm_bindgen_test]
 async fn counter() -> Result<(), JsValue> {
     let state = Writeable::new(0i32);
-    state.subscribe(|x| spiderweb::println!("{x}"));
+    state.subscribe(|x| spiderweb::println!("{x}")).forget();
 
     let element = body().create_component_shared("div", state)?;
     let element = element.as_ref();
 
     let plus = element.append_child(Element::new("button"))?;
-    plus.add_event_listener("click", |x| *x += 1);
+    plus.add_event_listener::<Click>(|_, x| *x += 1);
 
     let minus = element.append_child(Element::new("button"))?;
-    minus.add_event_listener("click", |x| *x -= 1);
+    minus.add_event_listener::<Click>(|ev, x| {
+        if !ev.shift_key() {
+            *x -= 1
+        }
+    });
 
     return Ok(());
 }
 
 // TODO test `!Unpin` states
+
+#[wasm_bindgen_test]
+fn keyed_list() -> Result<(), JsValue> {
+    let items = Writeable::new(vec![(1, "one"), (2, "two"), (3, "three")]);
+
+    let list = body().append_child_shared(client! { <ul id={"keyed-list"}></ul> }?)?;
+    list.bind_list(&items, |(key, _)| *key, |(_, name)| {
+        client! { <li>{*name}</li> }.unwrap()
+    })?;
+    let before = query_selector_all("#keyed-list > li");
+    assert_eq!(texts(&before), ["one", "two", "three"]);
+
+    items.update(|x| {
+        x.swap(0, 2);
+        x.remove(1);
+        x.push((4, "four"));
+    });
+
+    // Items that are kept reuse their element, wherever they moved to
+    let after = query_selector_all("#keyed-list > li");
+    assert_eq!(texts(&after), ["three", "one", "four"]);
+    assert!(after[0] == before[2]);
+    assert!(after[1] == before[0]);
+    assert!(before.iter().all(|x| *x != after[2]));
+    assert!(before[1].parent_node().is_none());
+
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn reactive_attributes() -> Result<(), JsValue> {
+    let title = Writeable::new(String::from("Hello"));
+    let hidden = Writeable::new(false);
+    let active = Writeable::new(true);
+
+    let element = client! {
+        <div id={"reactive-attributes"} title={&title} hidden={&hidden} tabindex={0}>
+            {"Hover me"}
+        </div>
+    }?;
+    let color = title.map(|x| String::from(if x == "Hello" { "red" } else { "blue" }));
+    element.bind_class("active", &active)?;
+    element.bind_style("color", &color)?;
+    element.set_property("draggable", true)?;
+    body().append_child(element)?;
+
+    let node = query_selector("#reactive-attributes").unwrap();
+    assert_eq!(node.get_attribute("title").as_deref(), Some("Hello"));
+    assert_eq!(node.get_attribute("hidden"), None);
+    assert_eq!(node.get_attribute("tabindex").as_deref(), Some("0"));
+    assert_eq!(node.get_attribute("class").as_deref(), Some("active"));
+    assert_eq!(node.get_attribute("style").as_deref(), Some("color: red;"));
+    assert!(node.draggable());
+
+    title.set(String::from("World"));
+    assert_eq!(node.get_attribute("title").as_deref(), Some("World"));
+    assert_eq!(node.get_attribute("style").as_deref(), Some("color: blue;"));
+
+    hidden.set(true);
+    assert_eq!(node.get_attribute("hidden").as_deref(), Some(""));
+
+    active.set(false);
+    assert_eq!(node.get_attribute("class").as_deref(), Some(""));
+
+    hidden.set(false);
+    assert_eq!(node.get_attribute("hidden"), None);
+
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn typed_events() -> Result<(), JsValue> {
+    let value = Writeable::new(String::new());
+
+    let input = body().append_child_shared(Element::new("input"))?;
+    let _listener = input.add_event_listener::<spiderweb::dom::event::KeyDown>(|ev| {
+        if ev.key() == "Enter" {
+            ev.prevent_default()
+        }
+    });
+
+    let element = client! {
+        <form on:submit={|ev: spiderweb::dom::event::Event| ev.prevent_default()}>
+            <input on:input={move |ev: InputEvent| value.set(ev.value().unwrap_or_default())} />
+        </form>
+    }?;
+    body().append_child(element)?;
+
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn removable_listeners() -> Result<(), JsValue> {
+    use spiderweb::{dom::event::{ListenerOptions, MouseEvent}, sync::abort::AbortController};
+    use std::{cell::RefCell, rc::Rc};
+
+    let clicks = Rc::new(RefCell::new(Vec::new()));
+    let record = |name: &'static str| {
+        let clicks = clicks.clone();
+        move |_: MouseEvent| clicks.borrow_mut().push(name)
+    };
+
+    let button = body().append_child_shared(client! { <button id={"removable-listeners"}></button> }?)?;
+    button.add_event_listener::<Click>(record("removed")).remove();
+
+    let once = ListenerOptions { once: true, ..Default::default() };
+    let _ = button.add_event_listener_with_options::<Click>(&once, record("once"));
+
+    let controller = AbortController::new();
+    let aborted = ListenerOptions { signal: Some(controller.signal()), ..Default::default() };
+    let _ = button.add_event_listener_with_options::<Click>(&aborted, record("aborted"));
+
+    let _ = button.add_event_listener::<Click>(record("kept"));
+    let owned = button.add_event_listener::<Click>(record("owned")).into_owned();
+
+    let node = query_selector("#removable-listeners").unwrap();
+    node.click();
+    assert_eq!(clicks.take(), ["once", "aborted", "kept", "owned"]);
+
+    controller.abort();
+    owned.remove();
+    node.click();
+    assert_eq!(clicks.take(), ["kept"]);
+
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn mounted_elements() -> Result<(), JsValue> {
+    use std::{cell::Cell, rc::Rc};
+
+    let list = body().append_child_shared(client! { <ul id={"mounted-list"}></ul> }?)?;
+    let other = body().append_child_shared(client! { <ol id={"mounted-other"}></ol> }?)?;
+
+    let first = list.append_child(Element::new("li"))?;
+    first.add_text("first")?;
+    let second = first.insert_before(Element::new("li"))?;
+    second.add_text("second")?;
+    assert_eq!(texts(&query_selector_all("#mounted-list > li")), ["second", "first"]);
+
+    let second = second.replace_with(Element::new("li"))?;
+    second.add_text("replaced")?;
+    assert_eq!(texts(&query_selector_all("#mounted-list > li")), ["replaced", "first"]);
+
+    // Listeners of removed elements are dropped with them
+    let clicks = Rc::new(Cell::new(0));
+    let counter = clicks.clone();
+    let _ = second.add_event_listener::<Click>(move |_| counter.set(counter.get() + 1));
+    let removed = query_selector("#mounted-list > li").unwrap();
+    removed.click();
+    assert_eq!(clicks.get(), 1);
+
+    second.remove()?;
+    assert_eq!(texts(&query_selector_all("#mounted-list > li")), ["first"]);
+    assert!(removed.parent_node().is_none());
+    removed.click();
+    assert_eq!(clicks.get(), 1);
+
+    // Moved elements keep their node and state
+    let first_node = query_selector("#mounted-list > li").unwrap();
+    let moved = first.move_to(&*other)?;
+    moved.add_text(" (moved)")?;
+    assert!(query_selector_all("#mounted-list > li").is_empty());
+    assert_eq!(texts(&query_selector_all("#mounted-other > li")), ["first (moved)"]);
+    assert!(query_selector("#mounted-other > li") == Some(first_node));
+
+    other.clear()?;
+    assert!(query_selector_all("#mounted-other > li").is_empty());
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn conditional_rendering() -> Result<(), JsValue> {
+    use spiderweb::dom::Show;
+
+    let visible = Writeable::new(true);
+    let count = Writeable::new(0u32);
+
+    let element = client! {
+        <div id={"conditional-rendering"}>
+            if &visible {
+                <p>{"Visible"}</p>
+            } else {
+                <p>{"Hidden"}</p>
+            }
+            match &count {
+                0 => <span>{"None"}</span>,
+                1 | 2 => <span>{"A few"}</span>,
+                n if *n < 10 => { <span>{n.to_string()}</span> }
+                _ => <span>{"Many"}</span>
+            }
+        </div>
+    }?;
+
+    let element = element.append_child_inner(Show::new(&visible, || Element::new("hr")))?;
+    element.bind_if(&visible, || Element::new("b"), || Element::new("i"))?;
+    body().append_child(element)?;
+
+    let children = || outer_htmls(&query_selector_all("#conditional-rendering > *"));
+    assert_eq!(children(), ["<p>Visible</p>", "<span>None</span>", "<hr>", "<b></b>"]);
+
+    visible.set(false);
+    assert_eq!(children(), ["<p>Hidden</p>", "<span>None</span>", "<i></i>"]);
+
+    count.set(5);
+    assert_eq!(children(), ["<p>Hidden</p>", "<span>5</span>", "<i></i>"]);
+
+    count.set(12);
+    assert_eq!(children(), ["<p>Hidden</p>", "<span>Many</span>", "<i></i>"]);
+
+    // Branches are rendered in place of the previous ones, whichever region changes
+    visible.set(true);
+    assert_eq!(children(), ["<p>Visible</p>", "<span>Many</span>", "<hr>", "<b></b>"]);
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn lifecycle_hooks() -> Result<(), JsValue> {
+    use std::{cell::Cell, rc::Rc};
+
+    let mounts = Rc::new(Cell::new(0));
+    let cleanups = Rc::new(Cell::new(0));
+
+    let root = Element::new("div");
+    let child = root.append_child(Element::new("p"))?;
+    child.on_mount({
+        let mounts = mounts.clone();
+        move || mounts.set(mounts.get() + 1)
+    });
+    child.on_unmount({
+        let mounts = mounts.clone();
+        move || mounts.set(mounts.get() - 1)
+    });
+    child.on_cleanup({
+        let cleanups = cleanups.clone();
+        move || cleanups.set(cleanups.get() + 1)
+    });
+    assert_eq!(mounts.get(), 0);
+
+    let root = body().append_child_shared(root)?;
+    assert!(root.is_mounted());
+    assert_eq!(mounts.get(), 1);
+
+    let comp = root.create_component("section", Vec::<&str>::new())?;
+    comp.as_ref().on_mount(|x| x.push("mounted"));
+    comp.as_ref().on_cleanup(|x| assert_eq!(x, &["mounted"]));
+    drop(comp);
+
+    root.remove()?;
+    assert_eq!(mounts.get(), 0);
+    assert_eq!(cleanups.get(), 1);
+
+    return Ok(());
+}
+
+#[derive(Default)]
+struct GreetingProps {
+    name: String,
+    excited: bool,
+}
+
+#[spiderweb::component]
+fn Greeting(props: GreetingProps, children: spiderweb::dom::Children) -> Result<Element, JsValue> {
+    let punctuation = if props.excited { "!" } else { "." };
+    client! {
+        <p>
+            {"Hello, "}
+            {props.name}
+            {punctuation}
+            {children}
+        </p>
+    }
+}
+
+#[spiderweb::component]
+fn Divider() -> Element {
+    Element::new("hr")
+}
+
+#[wasm_bindgen_test]
+fn components() -> Result<(), JsValue> {
+    let element = client! {
+        <div>
+            <Greeting name={"Alex".into()} excited={true} />
+            <Divider />
+            <Greeting name={"Sam".into()} ..>
+                <i>{" How are you?"}</i>
+            </Greeting>
+        </div>
+    }?;
+
+    body().append_child(element)?;
+    return Ok(());
+}
+
+struct Theme {
+    color: &'static str,
+}
+
+#[spiderweb::component]
+fn Themed(_: spiderweb::dom::NoProps, children: spiderweb::dom::Children) -> Result<Element, JsValue> {
+    spiderweb::dom::provide_context(Theme { color: "rebeccapurple" });
+    client! { <section>{children}</section> }
+}
+
+#[spiderweb::component]
+fn ThemedLabel() -> Result<Element, JsValue> {
+    let theme = spiderweb::dom::use_context::<Theme>().expect("no theme was provided");
+    client! { <span style={format!("color: {}", theme.color)}>{"Themed"}</span> }
+}
+
+#[wasm_bindgen_test]
+fn context() -> Result<(), JsValue> {
+    use spiderweb::dom::use_context;
+
+    let visible = Writeable::new(false);
+    let element = client! {
+        <Themed>
+            <ThemedLabel />
+            if &visible {
+                <ThemedLabel />
+            }
+        </Themed>
+    }?;
+
+    assert!(use_context::<Theme>().is_none());
+    body().append_child(element)?;
+    visible.set(true);
+
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn portals() -> Result<(), JsValue> {
+    use spiderweb::dom::Portal;
+    use std::{cell::Cell, rc::Rc};
+
+    let container = body().append_child_shared(client! { <div id={"portal-container"}></div> }?)?;
+    container.set_attribute("style", "overflow: hidden")?;
+    body().append_child(client! { <section id={"portal-target"}></section> }?)?;
+
+    // The content is placed at the end of the body, while its listeners follow the portal
+    let clicks = Rc::new(Cell::new(0));
+    let modal = container.append_child(Portal::new(client! { <dialog>{"Modal"}</dialog> }?))?;
+    let counter = clicks.clone();
+    modal.add_event_listener::<Click>(move |_| counter.set(counter.get() + 1));
+    assert!(modal.is_mounted());
+    assert!(query_selector("#portal-container dialog").is_none());
+    assert_eq!(texts(&query_selector_all("body > dialog")), ["Modal"]);
+
+    query_selector("body > dialog").unwrap().click();
+    assert_eq!(clicks.get(), 1);
+
+    modal.remove()?;
+    assert!(query_selector("dialog").is_none());
+
+    // Portals are only placed into their target while their parent is mounted
+    let element = client! {
+        <div>
+            {Portal::to_selector("#portal-target", Element::new("aside"))?}
+        </div>
+    }?;
+    assert!(query_selector_all("#portal-target > *").is_empty());
+
+    let element = body().append_child_shared(element)?;
+    assert_eq!(outer_htmls(&query_selector_all("#portal-target > *")), ["<aside></aside>"]);
+
+    element.remove()?;
+    assert!(query_selector_all("#portal-target > *").is_empty());
+
+    return Ok(());
+}
+
+#[wasm_bindgen_test]
+fn mount_to_existing() -> Result<(), JsValue> {
+    use spiderweb::dom::mount_to;
+
+    let app = body().append_child_shared(Element::new("div"))?;
+    app.set_attribute("id", "app")?;
+    app.add_text("Server rendered")?;
+
+    let mount = mount_to("#app", client! { <main>{"Client rendered"}</main> }?)?;
+    assert!(mount.element().is_mounted());
+    mount.unmount()?;
+
+    let existing = Element::from_existing(app.html_node().clone());
+    existing.add_text(" and wrapped")?;
+
+    return Ok(());
+}
@@ -0,0 +1,42 @@
+#![cfg(not(target_family = "wasm"))]
+
+use spiderweb::{client, dom::{testing::assert_html, Element}};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+#[test]
+fn stale_handles() -> Result<(), JsValue> {
+    let list = Element::new("ul");
+    let first = list.append_child(client! { <li>{"first"}</li> }?)?;
+
+    // Handles to cleared children don't point to the children that reuse their slots
+    list.clear()?;
+    let second = list.append_child(client! { <li>{"second"}</li> }?)?;
+    assert!(first.insert_before(Element::new("li")).is_err());
+    assert!(first.replace_with(Element::new("li")).is_err());
+    assert_html(&list, "<ul><li>second</li></ul>");
+
+    second.remove()?;
+    assert_html(&list, "<ul></ul>");
+    return Ok(());
+}
+
+#[test]
+fn failed_removals() -> Result<(), JsValue> {
+    let cleanups = Rc::new(Cell::new(0));
+    let list = Element::new("ul");
+    let item = list.append_child(Element::new("li"))?;
+    let counter = cleanups.clone();
+    item.on_cleanup(move || counter.set(counter.get() + 1));
+
+    // The node is moved behind the element's back, so the renderer can't remove it from the list
+    let other = Element::new("div");
+    other.append_child(Element::from_existing(item.html_node().clone()))?;
+    assert!(item.remove().is_err());
+    assert_eq!(cleanups.get(), 0);
+
+    // The element is still owned by the list
+    list.clear()?;
+    assert_eq!(cleanups.get(), 1);
+    return Ok(());
+}
@@ -0,0 +1,187 @@
+#![cfg(not(target_family = "wasm"))]
+
+use futures::{channel::oneshot, executor::block_on, future::{join, pending}, FutureExt};
+use spiderweb::{client, dom::attribute::Property, form::{Field, Form}};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+fn required (x: &String) -> Result<(), String> {
+    match x.trim().is_empty() {
+        true => Err(String::from("Required")),
+        false => Ok(()),
+    }
+}
+
+#[test]
+fn field_state() {
+    let name = Field::new(String::new()).with_validator(required);
+    assert!(!name.is_dirty().get());
+    assert_eq!(name.error().with(Clone::clone), None);
+
+    name.value().set(String::from("Alex"));
+    assert!(name.is_dirty().get());
+    assert!(!name.is_touched().get());
+
+    name.value().set(String::from("  "));
+    assert_eq!(name.error().with(Clone::clone).as_deref(), Some("Required"));
+
+    // Returning to the initial value makes the field pristine again
+    name.value().set(String::new());
+    assert!(!name.is_dirty().get());
+
+    name.touch();
+    assert!(name.is_touched().get());
+
+    name.reset();
+    assert!(!name.is_touched().get());
+    assert_eq!(name.error().with(Clone::clone), None);
+    assert!(block_on(Field::new(1u8).validate()));
+}
+
+#[test]
+fn async_validation() {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    let my_requests = requests.clone();
+    let username = Field::new(String::new())
+        .with_validator(required)
+        .with_async_validator(move |x: String| {
+            let (send, recv) = oneshot::channel::<bool>();
+            my_requests.borrow_mut().push(send);
+            async move {
+                match recv.await {
+                    Ok(true) => Ok(()),
+                    _ => Err(format!("'{x}' is taken")),
+                }
+            }
+        });
+
+    // Asynchronous validators only run once the synchronous ones succeed
+    assert!(!block_on(username.validate()));
+    assert!(requests.borrow().is_empty());
+
+    username.value().set(String::from("admin"));
+    let validation = username.validate();
+    let answer = requests.borrow_mut().pop().unwrap();
+    let (valid, _) = block_on(join(validation, async {
+        assert!(username.is_validating().get());
+        answer.send(false).unwrap();
+    }));
+
+    assert!(!valid);
+    assert!(!username.is_validating().get());
+    assert_eq!(username.error().with(Clone::clone).as_deref(), Some("'admin' is taken"));
+
+    // Outdated validations don't overwrite the result of newer ones
+    let outdated = username.validate();
+    let answer = requests.borrow_mut().pop().unwrap();
+    username.value().set(String::from("alex"));
+    answer.send(false).unwrap();
+    assert!(!block_on(outdated));
+    assert_eq!(username.error().with(Clone::clone), None);
+}
+
+#[test]
+fn submission() {
+    let submitted = Rc::new(RefCell::new(Vec::new()));
+    let email = Field::new(String::new())
+        .with_validator(required)
+        .with_async_validator(|x: String| async move {
+            x.contains('@').then_some(()).ok_or_else(|| String::from("Invalid email"))
+        });
+    let age = Field::new(0u32);
+
+    let my_submitted = submitted.clone();
+    let my_email = email.clone();
+    let form = Form::new()
+        .with_field(&email)
+        .with_field(&age)
+        .on_submit(move || {
+            let email = my_email.value().with(Clone::clone);
+            my_submitted.borrow_mut().push(email.clone());
+            async move {
+                match email.ends_with(".invalid") {
+                    true => Err(String::from("Couldn't reach the server")),
+                    false => Ok(()),
+                }
+            }
+        });
+
+    assert!(!block_on(form.submit()));
+    assert!(email.is_touched().get() && age.is_touched().get());
+    assert!(!form.is_valid());
+    assert!(submitted.borrow().is_empty());
+
+    email.value().set(String::from("alex"));
+    assert!(!block_on(form.submit()));
+    assert_eq!(email.error().with(Clone::clone).as_deref(), Some("Invalid email"));
+
+    email.value().set(String::from("alex@example.invalid"));
+    assert!(!block_on(form.submit()));
+    assert_eq!(form.error().with(Clone::clone).as_deref(), Some("Couldn't reach the server"));
+
+    email.value().set(String::from("alex@example.com"));
+    assert!(block_on(form.submit()));
+    assert!(form.is_dirty() && form.is_valid());
+    assert_eq!(form.error().with(Clone::clone), None);
+    assert!(!form.is_submitting().get());
+    assert_eq!(submitted.borrow().len(), 2);
+
+    form.reset();
+    assert!(!form.is_dirty());
+    assert_eq!(email.value().with(Clone::clone), "");
+
+    // Dropping a submission in progress doesn't leave the form submitting
+    let stalled = Form::new().on_submit(pending);
+    assert!(stalled.submit().now_or_never().is_none());
+    assert!(!stalled.is_submitting().get());
+}
+
+#[test]
+fn pristine_fields() {
+    let name = Field::new(String::new()).with_validator(required);
+    let age = Field::new(0u32);
+    let form = Form::new().with_field(&name).with_field(&age);
+
+    // Fields that were never validated run their synchronous validators
+    assert!(!form.is_valid());
+    assert_eq!(name.error().with(Clone::clone), None);
+
+    name.value().set(String::from("Alex"));
+    assert!(form.is_valid());
+
+    // Fields with asynchronous validators aren't valid until they've been validated
+    let email = Field::new(String::from("alex@example.com")).with_async_validator(|_: String| async { Ok(()) });
+    let form = form.with_field(&email);
+    assert!(!form.is_valid());
+
+    assert!(block_on(email.validate()));
+    assert!(form.is_valid());
+}
+
+#[test]
+fn bindings() -> Result<(), JsValue> {
+    let name = Field::new(String::new()).with_validator(required);
+    let form = Form::new().with_field(&name);
+
+    let element = client! {
+        <form bind:form={form}>
+            <input bind:field={name} />
+        </form>
+    }?;
+
+    let input = element.html_node().query_selector("input")?.unwrap();
+    input.dispatch_event(&spiderweb::dom::event::Event::new("blur"));
+    assert!(name.is_touched().get());
+    assert_eq!(name.error().with(Clone::clone).as_deref(), Some("Required"));
+
+    input.input("Alex");
+    assert_eq!(name.error().with(Clone::clone), None);
+
+    // Submissions are handled by the form instead of navigating
+    assert!(!element.html_node().submit());
+    name.value().set(String::from("Sam"));
+    form.reset();
+    assert_eq!(input.get_property("value"), Some(Property::String(String::new())));
+
+    return Ok(());
+}
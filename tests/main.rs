@@ -1,3 +1,5 @@
+#![cfg(target_family = "wasm")]
+
 use futures::StreamExt;
 use spiderweb::{
     time::{Interval, Timeout, Instant, SystemTime}, task::sleep,
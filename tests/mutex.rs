@@ -1,3 +1,5 @@
+#![cfg(target_family = "wasm")]
+
 use std::{ops::AddAssign, time::Duration};
 use futures::{join, future::select};
 use spiderweb::{sync::Mutex, task::sleep};
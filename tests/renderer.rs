@@ -0,0 +1,209 @@
+#![cfg(not(target_family = "wasm"))]
+
+use spiderweb::{client, component, dom::{attribute::Property, event::{Event, ListenerOptions}, hydrate, Children, Element, Renderer}, state::Writeable};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+/// A scene graph, rendered as an indented outline
+#[derive(Clone)]
+struct Scene(Rc<SceneNode>);
+
+struct SceneNode {
+    name: RefCell<String>,
+    parent: RefCell<Option<std::rc::Weak<SceneNode>>>,
+    children: RefCell<Vec<Scene>>,
+}
+
+thread_local! {
+    static ROOT: Scene = Scene::new("root");
+}
+
+impl Scene {
+    fn new (name: &str) -> Self {
+        return Self(Rc::new(SceneNode {
+            name: RefCell::new(name.to_owned()),
+            parent: RefCell::new(None),
+            children: RefCell::new(Vec::new()),
+        }));
+    }
+
+    fn parent (&self) -> Option<Scene> {
+        self.0.parent.borrow().as_ref().and_then(std::rc::Weak::upgrade).map(Scene)
+    }
+
+    fn detach (&self) {
+        if let Some(parent) = self.0.parent.take().and_then(|x| x.upgrade()) {
+            parent.children.borrow_mut().retain(|x| x != self);
+        }
+    }
+
+    fn find (&self, name: &str) -> Option<Scene> {
+        if *self.0.name.borrow() == name {
+            return Some(self.clone());
+        }
+        return self.0.children.borrow().iter().find_map(|x| x.find(name));
+    }
+
+    fn outline (&self, depth: usize, out: &mut String) {
+        let name = self.0.name.borrow();
+        if !name.is_empty() {
+            out.push_str(&format!("{}{name}\n", "  ".repeat(depth)));
+        }
+        for child in self.0.children.borrow().iter() {
+            child.outline(depth + 1, out);
+        }
+    }
+}
+
+impl PartialEq for Scene {
+    fn eq (&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl AsRef<Scene> for Scene {
+    fn as_ref (&self) -> &Scene {
+        self
+    }
+}
+
+struct SceneRenderer;
+
+impl Renderer for SceneRenderer {
+    type Node = Scene;
+    type Element = Scene;
+    type Text = Scene;
+    type Listener = ();
+
+    fn create_element (tag: &str) -> Scene { Scene::new(tag) }
+    fn create_text (s: &str) -> Scene { Scene::new(&format!("{s:?}")) }
+    fn create_anchor () -> Scene { Scene::new("") }
+    fn set_text (text: &Scene, s: &str) { *text.0.name.borrow_mut() = format!("{s:?}") }
+
+    fn append_child (parent: &Scene, child: &Scene) -> Result<(), JsValue> {
+        Self::insert_before(parent, child, None)
+    }
+
+    fn insert_before (parent: &Scene, child: &Scene, reference: Option<&Scene>) -> Result<(), JsValue> {
+        child.detach();
+        *child.0.parent.borrow_mut() = Some(Rc::downgrade(&parent.0));
+        let mut children = parent.0.children.borrow_mut();
+        let idx = reference.and_then(|x| children.iter().position(|y| y == x)).unwrap_or(children.len());
+        children.insert(idx, child.clone());
+        return Ok(());
+    }
+
+    fn remove_child (_: &Scene, child: &Scene) -> Result<(), JsValue> {
+        child.detach();
+        return Ok(());
+    }
+
+    fn replace_child (parent: &Scene, child: &Scene, old: &Scene) -> Result<(), JsValue> {
+        Self::insert_before(parent, child, Some(old))?;
+        return Self::remove_child(parent, old);
+    }
+
+    fn clear (parent: &Scene) {
+        for child in parent.0.children.take() {
+            *child.0.parent.borrow_mut() = None;
+        }
+    }
+
+    fn parent_node (node: &Scene) -> Option<Scene> { node.parent() }
+    fn first_child (node: &Scene) -> Option<Scene> { node.0.children.borrow().first().cloned() }
+
+    fn next_sibling (node: &Scene) -> Option<Scene> {
+        let parent = node.parent()?;
+        let children = parent.0.children.borrow();
+        let idx = children.iter().position(|x| x == node)?;
+        return children.get(idx + 1).cloned();
+    }
+
+    fn is_connected (node: &Scene) -> bool {
+        ROOT.with(|root| node == root) || node.parent().is_some_and(|x| Self::is_connected(&x))
+    }
+
+    fn set_attribute (element: &Scene, name: &str, value: &str) -> Result<(), JsValue> {
+        element.0.name.borrow_mut().push_str(&format!(" {name}={value}"));
+        return Ok(());
+    }
+
+    fn remove_attribute (_: &Scene, _: &str) -> Result<(), JsValue> { Ok(()) }
+    fn set_property (_: &Scene, _: &str, _: &Property) -> Result<(), JsValue> { Ok(()) }
+    fn get_property (_: &Scene, _: &str) -> Option<Property> { None }
+    fn toggle_class (_: &Scene, _: &str, _: bool) -> Result<(), JsValue> { Ok(()) }
+    fn set_style (_: &Scene, _: &str, _: &str) -> Result<(), JsValue> { Ok(()) }
+    fn add_listener (_: &Scene, _: &str, _: Box<dyn FnMut(Event)>, _: &ListenerOptions) {}
+    fn remove_listener (_: &Scene, _: &str, _: (), _: bool) {}
+
+    fn root () -> Scene { ROOT.with(Clone::clone) }
+
+    fn query_selector (name: &str) -> Result<Option<Scene>, JsValue> {
+        return Ok(ROOT.with(|root| root.find(name)));
+    }
+}
+
+struct LayerProps {
+    name: String,
+}
+
+#[component]
+fn Layer<R: Renderer>(props: LayerProps, children: Children<R>) -> Result<Element<R>, JsValue> {
+    client!(R => <layer name={props.name}>{children}</layer>)
+}
+
+#[test]
+fn custom_renderer() -> Result<(), JsValue> {
+    let label = Writeable::new(String::from("Score: 0"));
+    let paused = Writeable::new(false);
+
+    let scene = client!(SceneRenderer =>
+        <stage>
+            <Layer name={"hud".into()}>
+                <sprite>{&label}</sprite>
+            </Layer>
+            if &paused {
+                <overlay />
+            }
+        </stage>
+    )?;
+
+    let outline = |element: &Element<SceneRenderer>| {
+        let mut out = String::new();
+        element.html_node().outline(0, &mut out);
+        out
+    };
+
+    assert_eq!(outline(&scene), "stage\n  layer name=hud\n    sprite\n      \"Score: 0\"\n");
+
+    label.set(String::from("Score: 10"));
+    paused.set(true);
+    assert_eq!(outline(&scene), "stage\n  layer name=hud\n    sprite\n      \"Score: 10\"\n  overlay\n");
+
+    // Generic components also render to the default renderer
+    let html = client! { <Layer name={"hud".into()}>{"Score"}</Layer> }?;
+    assert_eq!(html.to_html(), r#"<layer name="hud">Score</layer>"#);
+
+    return Ok(());
+}
+
+#[test]
+fn custom_hydration() -> Result<(), JsValue> {
+    let target = Scene::new("viewport");
+    SceneRenderer::append_child(&SceneRenderer::root(), &target)?;
+    SceneRenderer::append_child(&target, &Scene::new("placeholder"))?;
+
+    let outline = || {
+        let mut out = String::new();
+        target.outline(0, &mut out);
+        out
+    };
+
+    // Scenes can't adopt nodes, so the application replaces the existing ones until it's unmounted
+    let mount = hydrate("viewport", || client!(SceneRenderer => <stage />))?;
+    assert_eq!(outline(), "viewport\n  stage\n");
+
+    mount.unmount()?;
+    assert_eq!(outline(), "viewport\n  placeholder\n");
+    return Ok(());
+}
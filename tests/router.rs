@@ -0,0 +1,245 @@
+#![cfg(not(target_family = "wasm"))]
+
+use futures::channel::oneshot;
+use spiderweb::{
+    client,
+    dom::{event::Event, testing::{assert_html, run_until_stalled, take_error}, Switch},
+    router::{History, Link, LoadContext, LoadState, Location, MemoryHistory, Outlet, Params, Pattern, Route, Router},
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+#[test]
+fn patterns() {
+    let pattern = Pattern::new("/users/:id/posts/:post");
+    let params = pattern.matches("/users/alex/posts/7/").unwrap();
+    assert_eq!(params.get_str("id"), Some("alex"));
+    assert_eq!(params.get::<u32>("post"), Some(7));
+    assert_eq!(params.get::<u32>("id"), None);
+    assert!(pattern.matches("/users/alex/posts").is_none());
+    assert!(pattern.matches("/users/alex/comments/7").is_none());
+
+    // Segments are decoded before being matched
+    assert_eq!(Pattern::new("/tags/:tag").matches("/tags/caf%C3%A9").unwrap().get_str("tag"), Some("café"));
+    assert!(Pattern::new("/a b").matches("/a%20b").is_some());
+
+    let files = Pattern::new("/files/*path");
+    assert_eq!(files.matches("/files/a/b.txt").unwrap().get_str("path"), Some("a/b.txt"));
+    assert_eq!(files.matches("/files").unwrap().get_str("path"), Some(""));
+    assert!(Pattern::new("/").matches("").is_some());
+}
+
+#[test]
+fn locations() {
+    let location = Location::parse("/docs/guide/intro?lang=en&q=a+b%21#setup");
+    assert_eq!(location.path, "/docs/guide/intro");
+    assert_eq!(location.query_param("q").as_deref(), Some("a b!"));
+    assert_eq!(location.query_param("missing"), None);
+    assert_eq!(location.to_string(), "/docs/guide/intro?lang=en&q=a+b%21#setup");
+
+    assert_eq!(location.join("/about").to_string(), "/about");
+    assert_eq!(location.join("setup").to_string(), "/docs/guide/setup");
+    assert_eq!(location.join("../api/").to_string(), "/docs/api/");
+    assert_eq!(location.join("../../../..").to_string(), "/");
+    assert_eq!(location.join("?lang=fr").to_string(), "/docs/guide/intro?lang=fr");
+    assert_eq!(location.join("#usage").to_string(), "/docs/guide/intro?lang=en&q=a+b%21#usage");
+}
+
+fn routes () -> Vec<Route> {
+    vec![
+        Route::new("/", |_| client! { <h1>{"Home"}</h1> }),
+        Route::new("/users/:id", |params| {
+            let id = params.get_str("id").unwrap().to_owned();
+            client! {
+                <section>
+                    <h1>{id}</h1>
+                    {Outlet}
+                </section>
+            }
+        })
+        .with_child(Route::new("", |_| client! { <p>{"Profile"}</p> }))
+        .with_child(Route::new("posts/:post", |params| client! { <p>{format!("Post {}", params.get::<u32>("post").unwrap())}</p> })),
+        Route::new("/*rest", |_| client! {
+            <div>
+                <p>{"Not found"}</p>
+                <Link to={String::from("/users/sam")}>{"Sam"}</Link>
+            </div>
+        }),
+    ]
+}
+
+#[test]
+fn navigation() -> Result<(), JsValue> {
+    let history = MemoryHistory::new("/users/alex");
+    let router = Router::with_history(history.clone(), routes());
+    let element = client! { <main>{&router}</main> }?;
+    assert_html(&element, "<main><section><h1>alex</h1><p>Profile</p><!----></section><!----></main>");
+
+    router.navigate("alex/posts/3")?;
+    assert_html(&element, "<main><section><h1>alex</h1><p>Post 3</p><!----></section><!----></main>");
+    assert_eq!(router.params().get::<u32>("post"), Some(3));
+
+    router.replace("/")?;
+    assert_html(&element, "<main><h1>Home</h1><!----></main>");
+    assert_eq!(history.len(), 2);
+
+    // Moving through the history is picked up by the router
+    router.back()?;
+    assert_eq!(router.location().with(|x| x.path.clone()), "/users/alex");
+    assert_html(&element, "<main><section><h1>alex</h1><p>Profile</p><!----></section><!----></main>");
+    router.forward()?;
+    assert_html(&element, "<main><h1>Home</h1><!----></main>");
+    history.go(-1)?;
+    router.back()?;
+    assert_eq!(history.index(), 0);
+
+    // Navigating drops the entries that could be gone forward to
+    router.navigate("/missing?from=alex")?;
+    assert_eq!(history.len(), 2);
+    assert_html(&element, r#"<main><div><p>Not found</p><a href="/users/sam">Sam</a></div><!----></main>"#);
+    return Ok(())
+}
+
+#[test]
+fn links() -> Result<(), JsValue> {
+    let history = MemoryHistory::new("/missing");
+    let router = Router::with_history(history.clone(), routes());
+    let element = client! { <main>{&router}</main> }?;
+    let link = element.html_node().query_selector("a")?.unwrap();
+
+    // Clicks that would open a new tab are left alone
+    let event = Event::with_options("click", true, true).with_field("ctrlKey", true);
+    assert!(link.dispatch_event(&event));
+    assert_eq!(history.len(), 1);
+
+    assert!(!link.click());
+    assert_eq!(history.location().to_string(), "/users/sam");
+    assert_html(&element, "<main><section><h1>sam</h1><p>Profile</p><!----></section><!----></main>");
+
+    // Outlets need to be rendered by a route
+    assert!(client! { <div>{Outlet}</div> }.is_err());
+    assert_eq!(take_error().as_deref(), Some("Outlets must be rendered inside of a route"));
+    return Ok(())
+}
+
+#[test]
+fn loaders() -> Result<(), JsValue> {
+    let requests = Rc::new(RefCell::new(Vec::<(LoadContext, oneshot::Sender<Result<String, String>>)>::new()));
+    let my_requests = requests.clone();
+    let routes = vec![
+        Route::new("/", |_| client! { <h1>{"Home"}</h1> }),
+        Route::with_loader(
+            "/users/:id",
+            move |cx| {
+                let (send, recv) = oneshot::channel();
+                my_requests.borrow_mut().push((cx, send));
+                async move { recv.await.unwrap_or_else(|_| Err(String::from("Cancelled"))) }
+            },
+            |_, user| client! {
+                <section>
+                    {Switch::new(user, |x| x.clone(), |x| match x {
+                        LoadState::Loading => client! { <p>{"Loading"}</p> },
+                        LoadState::Loaded(name) => client! { <h1>{name.clone()}</h1> },
+                        LoadState::Failed(e) => client! { <p>{e.clone()}</p> },
+                    }.ok())}
+                </section>
+            },
+        ),
+    ];
+
+    let router = Router::with_history(MemoryHistory::new("/users/1"), routes);
+    let element = client! { <main>{&router}</main> }?;
+    assert_html(&element, "<main><section><p>Loading</p><!----></section><!----></main>");
+
+    let (cx, send) = requests.borrow_mut().pop().unwrap();
+    assert_eq!(cx.params().get::<u32>("id"), Some(1));
+    send.send(Ok(String::from("Alex"))).unwrap();
+    run_until_stalled();
+    assert_html(&element, "<main><section><h1>Alex</h1><!----></section><!----></main>");
+
+    router.navigate("/users/2")?;
+    let (_, send) = requests.borrow_mut().pop().unwrap();
+    send.send(Err(String::from("Not found"))).unwrap();
+    run_until_stalled();
+    assert_html(&element, "<main><section><p>Not found</p><!----></section><!----></main>");
+
+    // Navigating away aborts the pending load
+    router.navigate("/users/3")?;
+    router.navigate("/")?;
+    let (cx, send) = requests.borrow_mut().pop().unwrap();
+    assert!(cx.is_aborted());
+    run_until_stalled();
+    assert!(send.is_canceled());
+    assert_html(&element, "<main><h1>Home</h1><!----></main>");
+    return Ok(())
+}
+
+#[test]
+fn ready_loaders() -> Result<(), JsValue> {
+    let routes = vec![Route::with_loader(
+        "/posts/:id",
+        |cx| std::future::ready(cx.params().get::<u32>("id").ok_or("Invalid")),
+        |_, post| client! {
+            <article>
+                {Switch::new(post, |x| x.clone(), |x| match x {
+                    LoadState::Loading => client! { <p>{"Loading"}</p> },
+                    LoadState::Loaded(id) => client! { <h1>{format!("Post {id}")}</h1> },
+                    LoadState::Failed(e) => client! { <p>{*e}</p> },
+                }.ok())}
+            </article>
+        },
+    )];
+
+    // Loaders run in the background, so the view is first rendered while loading, like in the browser
+    let router = Router::with_history(MemoryHistory::new("/posts/1"), routes);
+    let element = client! { <main>{&router}</main> }?;
+    assert_html(&element, "<main><article><p>Loading</p><!----></article><!----></main>");
+    run_until_stalled();
+    assert_html(&element, "<main><article><h1>Post 1</h1><!----></article><!----></main>");
+
+    router.navigate("/posts/new")?;
+    assert_html(&element, "<main><article><p>Loading</p><!----></article><!----></main>");
+    run_until_stalled();
+    assert_html(&element, "<main><article><p>Invalid</p><!----></article><!----></main>");
+    return Ok(())
+}
+
+#[test]
+fn lazy_routes() -> Result<(), JsValue> {
+    let (send, recv) = oneshot::channel::<&'static str>();
+    let routes = vec![
+        Route::new("/", |_| client! { <h1>{"Home"}</h1> }),
+        Route::lazy("/about", || async { |_: &Params| client! { <h1>{"About"}</h1> } }),
+        Route::lazy_with_fallback(
+            "/users/:id",
+            move || async move {
+                let prefix = recv.await.unwrap();
+                move |params: &Params| client! { <h1>{format!("{prefix} {}", params.get_str("id").unwrap())}</h1> }
+            },
+            |_| client! { <p>{"Loading"}</p> },
+        ),
+    ];
+
+    // Lazy routes render nothing until their view is loaded
+    let router = Router::with_history(MemoryHistory::new("/about"), routes);
+    let element = client! { <main>{&router}</main> }?;
+    assert_html(&element, "<main><!----></main>");
+    run_until_stalled();
+    assert_html(&element, "<main><h1>About</h1><!----></main>");
+
+    // ...or their fallback, if they have one
+    router.navigate("/users/1")?;
+    run_until_stalled();
+    assert_html(&element, "<main><p>Loading</p><!----></main>");
+    send.send("User").unwrap();
+    run_until_stalled();
+    assert_html(&element, "<main><h1>User 1</h1><!----></main>");
+
+    // Loaded views are kept by their routes
+    router.navigate("/")?;
+    router.navigate("/users/2")?;
+    assert_html(&element, "<main><h1>User 2</h1><!----></main>");
+    router.navigate("/about")?;
+    assert_html(&element, "<main><h1>About</h1><!----></main>");
+    return Ok(())
+}
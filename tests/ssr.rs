@@ -0,0 +1,202 @@
+#![cfg(not(target_family = "wasm"))]
+
+use spiderweb::{client, dom::{hydrate, testing::assert_html, Element, Children}, state::{Readable, Writeable}};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+struct CardProps {
+    title: String,
+}
+
+#[spiderweb::component]
+fn Card(props: CardProps, children: Children) -> Result<Element, JsValue> {
+    client! {
+        <article>
+            <h2>{props.title}</h2>
+            {children}
+        </article>
+    }
+}
+
+#[test]
+fn render_to_html() -> Result<(), JsValue> {
+    let name = Writeable::new(String::from("<Alex>"));
+    let highlighted = Writeable::new(true);
+    let visible = Writeable::new(false);
+
+    let element = client! {
+        <div id={"app"} title={"\"quoted\" & more"}>
+            <p>{"Hello, "}{&name}</p>
+            <input name={"agree"} disabled={true} />
+            if &visible {
+                <b>{"Visible"}</b>
+            } else {
+                <i>{"Hidden"}</i>
+            }
+            <Card title={"Fish & chips".into()}>
+                <script>{"if (a < b) {}"}</script>
+            </Card>
+        </div>
+    }?;
+
+    element.bind_class("highlighted", &highlighted)?;
+    element.set_property("hidden", false)?;
+
+    assert_eq!(
+        element.to_html(),
+        concat!(
+            r#"<div id="app" title="&quot;quoted&quot; &amp; more" class="highlighted">"#,
+            "<p>Hello, &lt;Alex&gt;</p>",
+            "<input name=\"agree\" disabled=\"\">",
+            "<i>Hidden</i><!---->",
+            "<article><h2>Fish &amp; chips</h2><script>if (a < b) {}</script></article>",
+            "</div>",
+        )
+    );
+
+    name.set(String::from("Sam"));
+    highlighted.set(false);
+    visible.set(true);
+
+    assert_eq!(
+        element.to_html(),
+        concat!(
+            r#"<div id="app" title="&quot;quoted&quot; &amp; more" class="">"#,
+            "<p>Hello, Sam</p>",
+            "<input name=\"agree\" disabled=\"\">",
+            "<b>Visible</b><!---->",
+            "<article><h2>Fish &amp; chips</h2><script>if (a < b) {}</script></article>",
+            "</div>",
+        )
+    );
+
+    return Ok(());
+}
+
+#[test]
+fn lists_and_properties() -> Result<(), JsValue> {
+    let items = Writeable::new(vec!["one", "two"]);
+
+    let list = Element::new("ul");
+    list.bind_list(&items, |x| *x, |x| {
+        let item = Element::new("li");
+        item.add_text(x).unwrap();
+        item
+    })?;
+
+    let input = Element::new("input");
+    input.set_property("value", "typed")?;
+    input.bind_style("color", &Writeable::new("red"))?;
+
+    let root = Element::new("form");
+    root.append_child(list)?;
+    root.append_child(input)?;
+    assert_eq!(
+        root.to_html(),
+        r#"<form><ul><li>one</li><li>two</li><!----></ul><input style="color: red;" value="typed"></form>"#
+    );
+
+    items.set(vec!["two", "three", "one"]);
+    assert_eq!(
+        root.to_html(),
+        r#"<form><ul><li>two</li><li>three</li><li>one</li><!----></ul><input style="color: red;" value="typed"></form>"#
+    );
+
+    return Ok(());
+}
+
+fn app (name: &Readable<String>, items: &Readable<Vec<&'static str>>, clicks: Rc<Cell<u32>>) -> Result<Element, JsValue> {
+    let root = client! {
+        <div>
+            <p class={"greeting"} on:click={move || clicks.set(clicks.get() + 1)}>
+                {"Hello, "}
+                <b>{name}</b>
+            </p>
+        </div>
+    }?;
+
+    let list = Element::new("ul");
+    list.bind_list(items, |x| *x, |x| client! { <li>{*x}</li> }.unwrap())?;
+    root.append_child(list)?;
+    return Ok(root);
+}
+
+#[test]
+fn hydration() -> Result<(), JsValue> {
+    // The server renders the same application into the target
+    let target = Element::new("main");
+    target.append_child(app(&Writeable::new(String::from("Alex")), &Writeable::new(vec!["one", "two"]), Rc::default())?)?;
+    let markup = target.to_html();
+
+    let node = target.html_node().clone();
+    let paragraph = node.query_selector("p")?.unwrap();
+    let items = node.query_selector_all("li")?;
+
+    let name = Writeable::new(String::from("Alex"));
+    let list = Writeable::new(vec!["one", "two"]);
+    let clicks = Rc::new(Cell::new(0));
+    let mount = hydrate(&node, || app(&name, &list, clicks.clone()))?;
+
+    // Nodes are adopted rather than recreated
+    assert_eq!(mount.element().to_html(), markup);
+    assert_eq!(node.query_selector("p")?.as_ref(), Some(&paragraph));
+    assert_eq!(node.query_selector_all("li")?, items);
+
+    // Listeners and bindings are attached to the adopted nodes
+    paragraph.click();
+    assert_eq!(clicks.get(), 1);
+
+    name.set(String::from("Sam"));
+    list.update(|x| x.push("three"));
+    assert_html(mount.element(), r#"
+        <main><div>
+            <p class="greeting">Hello, <b>Sam</b></p>
+            <ul><li>one</li><li>two</li><li>three</li><!----></ul>
+        </div></main>
+    "#);
+    assert_eq!(node.query_selector("p")?.as_ref(), Some(&paragraph));
+    assert_eq!(node.query_selector_all("li")?[..2], items[..]);
+
+    // Unmounting puts the adopted nodes back, without their listeners
+    mount.unmount()?;
+    assert_eq!(node.query_selector("p")?.as_ref(), Some(&paragraph));
+    paragraph.click();
+    assert_eq!(clicks.get(), 1);
+    return Ok(());
+}
+
+#[test]
+fn hydration_mismatches() -> Result<(), JsValue> {
+    let target = client! {
+        <main>
+            <div>
+                <h1>{"Title"}</h1>
+                <span>{"Stale"}</span>
+                <p>{"Server"}</p>
+                <footer>{"Leftover"}</footer>
+            </div>
+        </main>
+    }?;
+
+    let node = target.html_node().clone();
+    let title = node.query_selector("h1")?.unwrap();
+
+    let paragraph = node.query_selector("p")?.unwrap();
+
+    let mount = hydrate(&node, || client! {
+        <div>
+            <h1>{"Title"}</h1>
+            <em><b>{"New"}</b></em>
+            <p>{"Client"}</p>
+        </div>
+    })?;
+
+    // Mismatched nodes are replaced, the following ones are still adopted, and leftovers are removed
+    assert_html(mount.element(), "<main><div><h1>Title</h1><em><b>New</b></em><p>Client</p></div></main>");
+    assert_eq!(node.query_selector("p")?.as_ref(), Some(&paragraph));
+    assert_eq!(node.query_selector("h1")?.as_ref(), Some(&title));
+
+    drop(mount);
+    assert_eq!(node.query_selector("h1")?.as_ref(), Some(&title));
+    return Ok(());
+}
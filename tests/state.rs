@@ -0,0 +1,207 @@
+use std::{cell::Cell, rc::Rc};
+use spiderweb::state::{batch, computed, Writeable};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn computed_multiple_sources() {
+    let a = Rc::new(Writeable::new(1));
+    let b = Rc::new(Writeable::new(2));
+
+    let sum = computed({
+        let (a, b) = (a.clone(), b.clone());
+        move || a.get() + b.get()
+    });
+    assert_eq!(sum.get(), 3);
+
+    a.set(10);
+    assert_eq!(sum.get(), 12);
+
+    b.set(20);
+    assert_eq!(sum.get(), 30);
+}
+
+#[wasm_bindgen_test]
+fn computed_dynamic_dependencies() {
+    let cond = Rc::new(Writeable::new(true));
+    let a = Rc::new(Writeable::new(1));
+    let b = Rc::new(Writeable::new(2));
+    let runs = Rc::new(Cell::new(0));
+
+    let value = computed({
+        let (cond, a, b, runs) = (cond.clone(), a.clone(), b.clone(), runs.clone());
+        move || {
+            runs.set(runs.get() + 1);
+            match cond.get() {
+                true => a.get(),
+                false => b.get(),
+            }
+        }
+    });
+    assert_eq!(value.get(), 1);
+
+    // `b` isn't a dependency yet
+    b.set(3);
+    assert_eq!(runs.get(), 1);
+
+    cond.set(false);
+    assert_eq!(value.get(), 3);
+    assert_eq!(runs.get(), 2);
+
+    // `a` is no longer a dependency
+    a.set(4);
+    assert_eq!(runs.get(), 2);
+
+    b.set(5);
+    assert_eq!(value.get(), 5);
+    assert_eq!(runs.get(), 3);
+}
+
+#[wasm_bindgen_test]
+fn batch_notifies_once() {
+    let a = Writeable::new(1);
+    let b = Writeable::new(2);
+    let calls = Rc::new(Cell::new(0));
+
+    let _sub = a.subscribe({
+        let calls = calls.clone();
+        move |_| calls.set(calls.get() + 1)
+    });
+
+    batch(|| {
+        a.set(3);
+        b.set(4);
+        a.set(5);
+        assert_eq!(calls.get(), 0);
+    });
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(a.get() + b.get(), 9);
+}
+
+#[wasm_bindgen_test]
+fn diamond_is_glitch_free() {
+    let a = Rc::new(Writeable::new(1));
+    let double = a.map(|x| 2 * x);
+    let triple = a.map(|x| 3 * x);
+
+    let sum = computed({
+        let (double, triple) = (double.clone(), triple.clone());
+        move || double.get() + triple.get()
+    });
+
+    let seen = Rc::new(Cell::new(Vec::new()));
+    let _sub = sum.subscribe({
+        let seen = seen.clone();
+        move |x| {
+            let mut values = seen.take();
+            values.push(*x);
+            seen.set(values);
+        }
+    });
+
+    a.set(2);
+    a.set(3);
+    assert_eq!(seen.take(), vec![10, 15]);
+}
+
+#[wasm_bindgen_test]
+fn drop_subscription() {
+    let state = Writeable::new(0);
+    let calls = Rc::new(Cell::new(0));
+
+    let sub = state.subscribe({
+        let calls = calls.clone();
+        move |_| calls.set(calls.get() + 1)
+    });
+
+    state.set(1);
+    drop(sub);
+    state.set(2);
+    assert_eq!(calls.get(), 1);
+
+    state
+        .subscribe({
+            let calls = calls.clone();
+            move |_| calls.set(calls.get() + 1)
+        })
+        .forget();
+
+    state.set(3);
+    assert_eq!(calls.get(), 2);
+}
+
+#[wasm_bindgen_test]
+fn set_inside_subscriber() {
+    let state = Rc::new(Writeable::new(0));
+    let seen = Rc::new(Cell::new(Vec::new()));
+
+    let _sub = state.subscribe({
+        let (state, seen) = (Rc::downgrade(&state), seen.clone());
+        move |x| {
+            let mut values = seen.take();
+            values.push(*x);
+            seen.set(values);
+
+            if *x < 3 {
+                state.upgrade().unwrap().set(x + 1);
+            }
+        }
+    });
+
+    state.set(1);
+    assert_eq!(state.get(), 3);
+    assert_eq!(seen.take(), vec![1, 2, 3]);
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn update_inside_with() {
+    let state = Writeable::new(0);
+    state.with(|_| state.update(|x| *x += 1));
+}
+
+// Panics abort on WebAssembly, so recovering from them can only be tested natively
+#[cfg(not(target_family = "wasm"))]
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn panic_inside_batch() {
+    let state = Writeable::new(0);
+    let seen = Rc::new(Cell::new(0));
+    let _sub = state.subscribe({
+        let seen = seen.clone();
+        move |x| seen.set(*x)
+    });
+
+    let result = catch_unwind(|| batch(|| panic!("batch")));
+    assert!(result.is_err());
+
+    // The batch was closed, so changes are propagated right away
+    state.set(1);
+    assert_eq!(seen.get(), 1);
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn panic_inside_computed() {
+    let source = Rc::new(Writeable::new(0));
+    let _doubled = computed({
+        let source = source.clone();
+        move || match source.get() {
+            0 => 0,
+            _ => panic!("computed"),
+        }
+    });
+
+    let result = catch_unwind(AssertUnwindSafe(|| source.set(1)));
+    assert!(result.is_err());
+
+    // Reads outside of a computation aren't tracked by the computation that panicked
+    let state = Writeable::new(0);
+    assert_eq!(state.get(), 0);
+    state.set(1);
+    assert_eq!(state.get(), 1);
+}
@@ -0,0 +1,155 @@
+#![cfg(not(target_family = "wasm"))]
+
+use spiderweb::{client, dom::{attribute::Property, event::{Event, InputEvent, ListenerOptions}, testing::{assert_html, mount, take_error}, Element, Portal}, state::Writeable};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsValue;
+
+#[test]
+fn dispatch_order() -> Result<(), JsValue> {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let record = |name: &'static str| {
+        let log = log.clone();
+        Box::new(move |ev: Event| log.borrow_mut().push((name, ev.current_target().unwrap().node_name()))) as Box<dyn FnMut(Event)>
+    };
+
+    let outer = Element::new("section");
+    let inner = outer.append_child(Element::new("button"))?;
+
+    let capture = ListenerOptions { capture: true, ..Default::default() };
+    outer.add_event_listener_boxed_with_options("click", &capture, record("capture"));
+    outer.add_event_listener_boxed("click", record("bubble"));
+    inner.add_event_listener_boxed("click", record("target"));
+    inner.add_event_listener_boxed_with_options("click", &capture, record("target capture"));
+
+    let event = Event::with_options("click", true, true);
+    assert!(inner.html_node().dispatch_event(&event));
+    assert_eq!(event.target().unwrap().node_name(), "BUTTON");
+    assert_eq!(event.current_target(), None);
+    assert_eq!(log.take(), [
+        ("capture", String::from("SECTION")),
+        ("target capture", String::from("BUTTON")),
+        ("target", String::from("BUTTON")),
+        ("bubble", String::from("SECTION")),
+    ]);
+
+    // Events that don't bubble only reach the target after the capture phase
+    inner.html_node().dispatch_event(&Event::new("click"));
+    assert_eq!(log.take().len(), 3);
+
+    return Ok(());
+}
+
+#[test]
+fn listener_options() -> Result<(), JsValue> {
+    let calls = Rc::new(RefCell::new(0));
+    let element = Element::new("form");
+
+    let once = ListenerOptions { once: true, ..Default::default() };
+    let counter = calls.clone();
+    element.add_event_listener_boxed_with_options("submit", &once, Box::new(move |_| *counter.borrow_mut() += 1));
+
+    let passive = ListenerOptions { passive: true, ..Default::default() };
+    element.add_event_listener_boxed_with_options("submit", &passive, Box::new(|ev| ev.prevent_default()));
+    assert!(element.html_node().submit());
+    assert!(element.html_node().submit());
+    assert_eq!(*calls.borrow(), 1);
+
+    let listener = element.add_event_listener_boxed("submit", Box::new(|ev| {
+        ev.prevent_default();
+        ev.stop_immediate_propagation();
+    })).into_owned();
+    let counter = calls.clone();
+    element.add_event_listener_boxed("submit", Box::new(move |_| *counter.borrow_mut() += 1));
+
+    assert!(!element.html_node().submit());
+    assert_eq!(*calls.borrow(), 1);
+
+    listener.remove();
+    assert!(element.html_node().submit());
+    assert_eq!(*calls.borrow(), 2);
+
+    return Ok(());
+}
+
+#[test]
+fn queries_and_interactions() -> Result<(), JsValue> {
+    let name = Rc::new(Writeable::new(String::new()));
+    let changes = Rc::new(RefCell::new(0));
+
+    let my_name = name.clone();
+    let form = client! {
+        <form id={"signup"}>
+            <label class={"field"}>
+                <input name={"name"} on:input={move |ev: InputEvent| my_name.set(ev.value().unwrap_or_default())} />
+            </label>
+            <label class={"field optional"}></label>
+        </form>
+    }?;
+
+    let checkbox = Element::new("input");
+    checkbox.set_attribute("type", "checkbox")?;
+    checkbox.set_attribute("name", "agree")?;
+    let counter = changes.clone();
+    checkbox.add_event_listener_boxed("change", Box::new(move |_| *counter.borrow_mut() += 1));
+    let label = form.append_child(Element::new("label"))?;
+    label.append_child(checkbox)?;
+
+    let root = form.html_node();
+    assert_eq!(root.query_selector_all(".field")?.len(), 2);
+    assert_eq!(root.query_selector_all("label.field > input")?.len(), 1);
+    assert_eq!(root.query_selector_all("form input, label")?.len(), 5);
+    assert_eq!(root.query_selector_all("#signup [type=checkbox]")?.len(), 1);
+    assert!(root.query_selector("form > input")?.is_none());
+    assert!(root.query_selector("label >").is_err());
+
+    root.query_selector("input[name=name]")?.unwrap().input("Alex");
+    assert_eq!(name.with(Clone::clone), "Alex");
+
+    let checkbox = root.query_selector(r#"label > [name="agree"]"#)?.unwrap();
+    assert!(checkbox.click());
+    assert_eq!(checkbox.get_property("checked"), Some(Property::Bool(true)));
+    assert_eq!(*changes.borrow(), 1);
+
+    // Canceled clicks leave checkboxes untouched
+    let target = Element::from_existing(checkbox.clone());
+    target.add_event_listener_boxed("click", Box::new(|ev| ev.prevent_default()));
+    assert!(!checkbox.click());
+    assert_eq!(checkbox.get_property("checked"), Some(Property::Bool(true)));
+    assert_eq!(*changes.borrow(), 1);
+
+    return Ok(());
+}
+
+#[test]
+fn mounting() -> Result<(), JsValue> {
+    let mounted = Rc::new(RefCell::new(false));
+    let element = client! { <main>
+        <h1>{"Title"}</h1>
+    </main> }?;
+
+    let flag = mounted.clone();
+    element.on_mount(move || *flag.borrow_mut() = true);
+
+    let app = mount(element)?;
+    assert!(*mounted.borrow());
+    assert_html(app.element(), "
+        <body>
+            <main><h1>Title</h1></main>
+        </body>
+    ");
+
+    app.unmount()?;
+    return Ok(());
+}
+
+#[test]
+fn logged_errors() {
+    let Err(e) = Portal::to_selector("#missing", Element::new("aside")) else {
+        panic!("the target shouldn't exist")
+    };
+
+    // Logging an error leaves its message for whoever handles it
+    spiderweb::eprintln!(&e);
+    assert_eq!(take_error().as_deref(), Some("no element matches '#missing'"));
+    assert_eq!(take_error(), None);
+}